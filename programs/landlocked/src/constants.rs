@@ -3,3 +3,30 @@ use anchor_lang::prelude::*;
 #[constant]
 pub const SEED: &str = "anchor";
 pub const USER_SEED: &str = "person";
+pub const MAX_ADMINS: usize = 5;
+pub const MAX_REGISTRAR_DISTRICTS: usize = 5;
+pub const MAX_DISTRICT_LENGTH: usize = 100;
+pub const MAX_REASON_LENGTH: usize = 200;
+pub const MAX_BENEFICIARIES: usize = 5;
+pub const MAX_COURT_GRANT_REFERENCE_LENGTH: usize = 50;
+pub const MAX_CO_OWNERS: usize = 5;
+pub const MAX_TITLE_NUMBER_LENGTH: usize = 15;
+// co-owner shares are expressed in basis points of the whole title
+pub const TOTAL_SHARE_BPS: u16 = 10_000;
+pub const MAX_SUBDIVISION_PARCELS: usize = 5;
+pub const MAX_AMALGAMATED_TITLES: usize = 5;
+pub const MAX_LEASES: usize = 5;
+pub const MAX_PERMITTED_USE_LENGTH: usize = 100;
+pub const MAX_EASEMENTS: usize = 5;
+pub const MAX_EASEMENT_DESCRIPTION_LENGTH: usize = 100;
+// parcel acreages must add up to the parent title's to within a ten-thousandth of an acre
+pub const ACREAGE_TOLERANCE: f64 = 0.0001;
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+// a proposal not executed within 7 days lapses and has to be proposed again
+pub const PROPOSAL_VALIDITY_DAYS: i64 = 7;
+// a land search is valid for 30 days unless admins configure otherwise
+pub const DEFAULT_SEARCH_VALIDITY_DAYS: u16 = 30;
+// registrar has 14 days to act on an escrow before anyone can unwind it and refund the buyer
+pub const DEFAULT_ESCROW_PERIOD_DAYS: u16 = 14;
+// an agreement is void if no escrow is created within 7 days
+pub const DEFAULT_AGREEMENT_VALIDITY_DAYS: u16 = 7;
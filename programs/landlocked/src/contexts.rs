@@ -1,7 +1,7 @@
 use crate::{
-    Admin, Agreement, Deposit, EscrowState, Registrar, USER_SEED, User, error::ProtocolError, state::{
-        AgreementIndex, Escrow, IdNumberClaim, OwnershipHistory, ProtocolState, TitleDeed, TitleForSale,
-        TitleNumberLookup,
+    Admin, Agreement, Deposit, EscrowState, Registrar, TOTAL_SHARE_BPS, USER_SEED, User, error::ProtocolError, state::{
+        AgreementIndex, AgreementRecord, Caveat, CaveatKind, CaveatStatus, Charge, Easement, Escrow, Lease, GiftTransfer, IdNumberClaim, OwnershipHistory, Proposal, ProtocolState,
+        SearchCertificate, Succession, Tenure, TitleDeed, TitleForSale, TitleNumberLookup,
     }
 };
 use anchor_lang::prelude::*;
@@ -39,6 +39,168 @@ pub struct ConfirmAdminAccount<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", protocol_state.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.executed_at.is_none() @ ProtocolError::ProposalAlreadyExecuted
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct AddAdmin<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAdmin<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// CHECK: wallet of the admin being removed - must match the proposal and receives the admin account rent
+    #[account(mut)]
+    pub former_admin: UncheckedAccount<'info>,
+    /// CHECK: admin account of the removed admin - closed by the handler if they confirmed it
+    #[account(
+        mut,
+        seeds = [b"admin", former_admin.key().as_ref()],
+        bump
+    )]
+    pub former_admin_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RotateAdmin<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// CHECK: wallet of the admin being rotated out - must match the proposal and receives the admin account rent
+    #[account(mut)]
+    pub former_admin: UncheckedAccount<'info>,
+    /// CHECK: admin account of the rotated out admin - closed by the handler if they confirmed it
+    #[account(
+        mut,
+        seeds = [b"admin", former_admin.key().as_ref()],
+        bump
+    )]
+    pub former_admin_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PauseProtocol<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolAlreadyPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct UnpauseProtocol<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        constraint = protocol_state.is_paused @ ProtocolError::ProtocolNotPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
 #[derive(Accounts)]
 #[instruction(address: Pubkey)]
 pub struct AddRegistrar<'info> {
@@ -53,10 +215,115 @@ pub struct AddRegistrar<'info> {
     )]
     pub registrar: Account<'info, Registrar>,
     pub admin: Account<'info, Admin>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
     pub protocol_state: Account<'info, ProtocolState>,
+    // proposal approving the registrar address and districts
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct SuspendRegistrar<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"registrar", registrar.authority.as_ref()],
+        bump = registrar.bump,
+        constraint = registrar.revoked_at.is_none() @ ProtocolError::RegistrarRevoked,
+        constraint = registrar.suspended_at.is_none() @ ProtocolError::RegistrarSuspended
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct ReinstateRegistrar<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"registrar", registrar.authority.as_ref()],
+        bump = registrar.bump,
+        constraint = registrar.revoked_at.is_none() @ ProtocolError::RegistrarRevoked,
+        constraint = registrar.suspended_at.is_some() @ ProtocolError::RegistrarNotSuspended
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRegistrar<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"registrar", registrar.authority.as_ref()],
+        bump = registrar.bump,
+        constraint = registrar.revoked_at.is_none() @ ProtocolError::RegistrarRevoked
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRegistrarDistricts<'info> {
+    pub authority: Signer<'info>, // authority must be an admin
+    #[account(
+        mut,
+        seeds = [b"registrar", registrar.authority.as_ref()],
+        bump = registrar.bump,
+        constraint = registrar.revoked_at.is_none() @ ProtocolError::RegistrarRevoked
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
 #[derive(Accounts)]
 pub struct ConfirmRegistrarAccount<'info> {
     #[account(mut)]
@@ -67,6 +334,7 @@ pub struct ConfirmRegistrarAccount<'info> {
         bump = registrar.bump,
         // registrar must not be already confirmed
         constraint = !registrar.is_active @ ProtocolError::RegistrarAlreadyConfirmed,
+        constraint = registrar.revoked_at.is_none() @ ProtocolError::RegistrarRevoked,
     )]
     pub registrar: Account<'info, Registrar>,
     pub protocol_state: Account<'info, ProtocolState>,
@@ -117,6 +385,8 @@ pub struct AssignTitleDeedToOwner<'info> {
     #[account(
         seeds = [b"registrar", authority.key().as_ref()],
         bump = registrar.bump,
+        constraint = registrar.revoked_at.is_none() @ ProtocolError::RegistrarRevoked,
+        constraint = registrar.suspended_at.is_none() @ ProtocolError::RegistrarSuspended,
     )]
     pub registrar: Account<'info, Registrar>,
     // TODO: owner must have permitted the transfer(through escrow process)
@@ -124,7 +394,8 @@ pub struct AssignTitleDeedToOwner<'info> {
         init,
         payer = authority,
         space = 8 + TitleDeed::INIT_SPACE,
-        seeds = [b"title_deed", new_owner_address.as_ref()],
+        // title deeds are keyed by title number so an owner can hold any number of titles
+        seeds = [b"title_deed", title_number.as_bytes()],
         bump
     )]
     pub title_deed: Account<'info, TitleDeed>,
@@ -137,6 +408,22 @@ pub struct AssignTitleDeedToOwner<'info> {
         bump
     )]
     pub ownership_history: Account<'info, OwnershipHistory>,
+    // index is created with the title so a title number can only be registered once
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TitleNumberLookup::INIT_SPACE,
+        seeds = [b"title_number_lookup", title_number.as_bytes()],
+        bump
+    )]
+    pub title_number_lookup: Account<'info, TitleNumberLookup>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
     pub system_program: Program<'info, System>,
 }
 
@@ -147,19 +434,38 @@ pub struct MarkTitleForSale<'info> {
     pub authority: Signer<'info>, // Must be the owner of the title deed
     #[account(
         mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
         // title deed must be owned by the authority
         constraint = title_deed.authority == authority.key() @ ProtocolError::Unauthorized,
         // seller must be the owner of the title deed
-        constraint = title_deed.owner.authority == authority.key() @ ProtocolError::Unauthorized
+        constraint = title_deed.owner.authority == authority.key() @ ProtocolError::Unauthorized,
+        // no dealings on a deceased owner's title
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        // caveats and court orders freeze all dealings on the title
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+        // co-owners' shares are sold or unlisted before the whole title is listed
+        constraint = title_deed.listed_shares == 0 @ ProtocolError::TitleIsForSale,
+        // a charged title can only be listed at a price that pays off its lenders
+        constraint = price >= title_deed.charged_amount @ ProtocolError::TitleIsCharged
     )]
     pub title_deed: Account<'info, TitleDeed>,
     #[account(
         // seller must be the authority
         constraint = seller.authority == authority.key() @ ProtocolError::Unauthorized,
         // seller must be the owner of the title deed
-        constraint = seller.authority == title_deed.owner.authority @ ProtocolError::Unauthorized
+        constraint = seller.authority == title_deed.owner.authority @ ProtocolError::Unauthorized,
+        // a deceased owner's titles pass through succession instead
+        constraint = seller.deceased_at.is_none() @ ProtocolError::OwnerDeceased
     )]
     pub seller: Account<'info, User>,
+    /// CHECK: seller's death record, keyed on their wallet - must not exist, whichever user account they deal through
+    #[account(
+        seeds = [b"death_record", authority.key().as_ref()],
+        bump,
+        constraint = death_record.data_is_empty() @ ProtocolError::OwnerDeceased,
+    )]
+    pub death_record: UncheckedAccount<'info>,
     #[account(
         init,
         payer = authority,
@@ -169,105 +475,278 @@ pub struct MarkTitleForSale<'info> {
         bump
     )]
     pub title_for_sale: Account<'info, TitleForSale>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
     pub system_program: Program<'info, System>,
 }
 
-/// Search title deed by title_number
-/// This allows buyers to search for title deeds by their title number
-// TODO: should search be done only on land/titles marked for sale?
+// co-owner lists their share of a title for sale
 #[derive(Accounts)]
-#[instruction(title_number: String)]
-pub struct SearchTitleDeedByNumber<'info> {
+pub struct MarkShareForSale<'info> {
     #[account(mut)]
-    pub authority: Signer<'info>,
-    /// Title deed being searched - caller must provide this
+    pub authority: Signer<'info>, // must be a co-owner of the title deed
     #[account(
-        constraint = title_deed.title_number == title_number @ ProtocolError::TitleAuthorityMismatch
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // title deed must not be held in escrow
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+        // no dealings on a deceased owner's title
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+        // lenders are only paid off from the sale of the whole title
+        constraint = title_deed.charged_amount == 0 @ ProtocolError::TitleIsCharged
     )]
     pub title_deed: Account<'info, TitleDeed>,
-    /// Lookup account - created lazily on first search
-    /// Account may not exist yet - handler will create it if needed
-    /// CHECK: Account may not be initialized yet - handler will check and create if needed
-    #[account(mut)]
-    pub title_number_lookup: UncheckedAccount<'info>,
     #[account(
-        mut,
-        constraint = searched_by.authority == authority.key() @ ProtocolError::Unauthorized
+        // seller must be the authority
+        constraint = seller.authority == authority.key() @ ProtocolError::Unauthorized,
+        // a deceased co-owner's share passes through succession instead
+        constraint = seller.deceased_at.is_none() @ ProtocolError::OwnerDeceased
     )]
-    pub searched_by: Account<'info, User>,
+    pub seller: Account<'info, User>,
+    /// CHECK: seller's death record, keyed on their wallet - must not exist, whichever user account they deal through
+    #[account(
+        seeds = [b"death_record", authority.key().as_ref()],
+        bump,
+        constraint = death_record.data_is_empty() @ ProtocolError::OwnerDeceased,
+    )]
+    pub death_record: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TitleForSale::INIT_SPACE,
+        seeds = [b"title_for_sale", seller.authority.key().as_ref(), title_deed.key().as_ref()],
+        bump
+    )]
+    pub title_for_sale: Account<'info, TitleForSale>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
     pub system_program: Program<'info, System>,
 }
 
-// Make an agreement for the sale of a title deed
-// we have many checks(constraints) to ensure that due process is followed
-// to reduce risk of fraud and potential disputes since land issues are sensitive.
-// TODO: time bound agreements - if no escrow is created within the time limit, the agreement is void.
+// owner takes the whole title off the market before it goes into escrow
 #[derive(Accounts)]
-#[instruction(price: u64)]
-pub struct MakeAgreement<'info> {
-    #[account(mut,
-        constraint = title_deed.authority == authority.key() @ ProtocolError::Unauthorized
-    )]
-    pub authority: Signer<'info>, // Must be the seller of the title deed - current land owner drafts the agreement
+pub struct DelistTitle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // must be the owner of the title deed
     #[account(
         mut,
-        // title deed for the land being sold must match the searched title deed
-        constraint = title_deed.key() == title_number_lookup.title_deed.key() @ ProtocolError::TitleNotMarkedForSale
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.owner.authority == authority.key() @ ProtocolError::Unauthorized,
+        // title deed must not be held in escrow
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::TitleAuthorityMismatch,
     )]
     pub title_deed: Account<'info, TitleDeed>,
     #[account(
         mut,
         seeds = [b"title_for_sale", authority.key().as_ref(), title_deed.key().as_ref()],
         bump = title_for_sale.bump,
-        // title marked for sale must be the same as the title deed in the agreement
-        constraint = title_for_sale.title_deed == title_deed.key() @ ProtocolError::TitleNotMarkedForSale
+        // share listings are withdrawn by unlisting the share instead
+        constraint = title_for_sale.share_bps == TOTAL_SHARE_BPS @ ProtocolError::InvalidShare,
+        close = authority,
     )]
     pub title_for_sale: Account<'info, TitleForSale>,
+    /// CHECK: only exists while an agreement is in progress on the title - handler checks it is absent
     #[account(
-        mut,
-        // seller must be the authority to initiate the agreement
-        constraint = seller.authority == authority.key() @ ProtocolError::Unauthorized,
-        // seller must match the seller in the title marked for sale
-        constraint = seller.authority == title_for_sale.seller.authority @ ProtocolError::Unauthorized
-    )]
-    pub seller: Account<'info, User>,
-    // TODO: add constraint for buyer
-    pub buyer: Account<'info, User>,
-    // TODO: allow multiple searches for the same title number
-    #[account(
-        mut,
-        seeds = [b"title_number_lookup", title_deed.title_number.as_bytes()],
-        bump = title_number_lookup.bump,
-        constraint = title_number_lookup.title_number == title_deed.title_number @ ProtocolError::TitleNotMarkedForSale
-    )]
-    pub title_number_lookup: Account<'info, TitleNumberLookup>,
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Agreement::INIT_SPACE,
-        seeds = [b"agreement", authority.key().as_ref(), buyer.authority.key().as_ref(), title_deed.key().as_ref(), price.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub agreement: Account<'info, Agreement>,
-    /// CHECK: AgreementIndex may already exist - handler will check and throw AgreementAlreadyExists if needed
-    /// PDA validation ensures correct account is passed
-    #[account(
-        mut,
         seeds = [b"agreement_index", title_deed.key().as_ref()],
         bump
     )]
     pub agreement_index: UncheckedAccount<'info>,
-    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
 }
 
+// co-owner withdraws their share listing before it is sold
 #[derive(Accounts)]
-#[instruction(price: u64)]
-pub struct SignAgreement<'info> {
+pub struct UnlistShare<'info> {
     #[account(mut)]
-    pub authority: Signer<'info>, // Must be the buyer of the agreement
+    pub authority: Signer<'info>, // the co-owner who listed the share
     #[account(
         mut,
-        constraint = title_deed.key() == agreement.title_deed @ ProtocolError::InvalidTitleDeed
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // title deed must not be held in escrow
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        mut,
+        seeds = [b"title_for_sale", authority.key().as_ref(), title_deed.key().as_ref()],
+        bump = title_for_sale.bump,
+        // the whole title is delisted by its owner instead
+        constraint = title_for_sale.share_bps < TOTAL_SHARE_BPS @ ProtocolError::InvalidShare,
+        close = authority,
+    )]
+    pub title_for_sale: Account<'info, TitleForSale>,
+    /// CHECK: only exists while an agreement is in progress on the title - handler checks it is absent
+    #[account(
+        seeds = [b"agreement_index", title_deed.key().as_ref()],
+        bump
+    )]
+    pub agreement_index: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+/// Search title deed by title_number
+/// This allows buyers to search for title deeds by their title number
+// TODO: should search be done only on land/titles marked for sale?
+#[derive(Accounts)]
+#[instruction(title_number: String)]
+pub struct SearchTitleDeedByNumber<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Title deed being searched - caller must provide this
+    #[account(
+        seeds = [b"title_deed", title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.title_number == title_number @ ProtocolError::TitleAuthorityMismatch
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        seeds = [b"title_number_lookup", title_number.as_bytes()],
+        bump = title_number_lookup.bump,
+        constraint = title_number_lookup.title_deed == title_deed.key() @ ProtocolError::TitleAuthorityMismatch
+    )]
+    pub title_number_lookup: Account<'info, TitleNumberLookup>,
+    /// Search certificate - created on the searcher's first search and reissued on later searches
+    /// CHECK: Account may not be initialized yet - handler will check and create if needed
+    #[account(
+        mut,
+        seeds = [b"search_certificate", title_deed.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub search_certificate: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = searched_by.authority == authority.key() @ ProtocolError::Unauthorized
+    )]
+    pub searched_by: Account<'info, User>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// Make an agreement for the sale of a title deed
+// we have many checks(constraints) to ensure that due process is followed
+// to reduce risk of fraud and potential disputes since land issues are sensitive.
+// agreements are time bound - if no escrow is created before expires_at the agreement is void.
+#[derive(Accounts)]
+#[instruction(price: u64)]
+pub struct MakeAgreement<'info> {
+    #[account(mut,
+        constraint = title_deed.co_owners.iter().any(|co_owner| co_owner.authority == authority.key()) @ ProtocolError::Unauthorized
+    )]
+    pub authority: Signer<'info>, // Must be the seller - an owner of the title deed drafts the agreement
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // title deed must not be held in escrow
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::Unauthorized,
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        mut,
+        seeds = [b"title_for_sale", authority.key().as_ref(), title_deed.key().as_ref()],
+        bump = title_for_sale.bump,
+        // title marked for sale must be the same as the title deed in the agreement
+        constraint = title_for_sale.title_deed == title_deed.key() @ ProtocolError::TitleNotMarkedForSale
+    )]
+    pub title_for_sale: Account<'info, TitleForSale>,
+    #[account(
+        mut,
+        // seller must be the authority to initiate the agreement
+        constraint = seller.authority == authority.key() @ ProtocolError::Unauthorized,
+        // seller must match the seller in the title marked for sale
+        constraint = seller.authority == title_for_sale.seller.authority @ ProtocolError::Unauthorized,
+        constraint = seller.deceased_at.is_none() @ ProtocolError::OwnerDeceased
+    )]
+    pub seller: Account<'info, User>,
+    /// CHECK: seller's death record, keyed on their wallet - must not exist, whichever user account they deal through
+    #[account(
+        seeds = [b"death_record", authority.key().as_ref()],
+        bump,
+        constraint = death_record.data_is_empty() @ ProtocolError::OwnerDeceased,
+    )]
+    pub death_record: UncheckedAccount<'info>,
+    // TODO: add constraint for buyer
+    pub buyer: Account<'info, User>,
+    // buyer's own search certificate for the title
+    #[account(
+        seeds = [b"search_certificate", title_deed.key().as_ref(), buyer.authority.as_ref()],
+        bump = search_certificate.bump,
+        // title deed for the land being sold must match the searched title deed
+        constraint = search_certificate.title_deed == title_deed.key() @ ProtocolError::TitleNotMarkedForSale
+    )]
+    pub search_certificate: Account<'info, SearchCertificate>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Agreement::INIT_SPACE,
+        seeds = [b"agreement", authority.key().as_ref(), buyer.authority.key().as_ref(), title_deed.key().as_ref(), price.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agreement: Account<'info, Agreement>,
+    /// CHECK: AgreementIndex may already exist - handler will check and throw AgreementAlreadyExists if needed
+    /// PDA validation ensures correct account is passed
+    #[account(
+        mut,
+        seeds = [b"agreement_index", title_deed.key().as_ref()],
+        bump
+    )]
+    pub agreement_index: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(price: u64)]
+pub struct SignAgreement<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // Must be the buyer of the agreement
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.key() == agreement.title_deed @ ProtocolError::InvalidTitleDeed,
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat
     )]
     pub title_deed: Account<'info, TitleDeed>,
     #[account(
@@ -278,6 +757,13 @@ pub struct SignAgreement<'info> {
         constraint = agreement.price == price @ ProtocolError::InvalidTitleDeed
     )]
     pub agreement: Account<'info, Agreement>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
 }
 
 #[derive(Accounts)]
@@ -290,6 +776,13 @@ pub struct CancelAgreement<'info> {
         close = authority,
     )]
     pub agreement: Account<'info, Agreement>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.key() == agreement.title_deed @ ProtocolError::InvalidTitleDeed,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
     #[account(
         mut,
         seeds = [b"agreement_index", agreement.title_deed.as_ref()],
@@ -297,6 +790,86 @@ pub struct CancelAgreement<'info> {
         bump
     )]
     pub agreement_index: Account<'info, AgreementIndex>,
+    // archived copy of the agreement for audit purposes
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgreementRecord::INIT_SPACE,
+        seeds = [b"agreement_record", title_deed.key().as_ref(), title_deed.total_agreements.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agreement_record: Account<'info, AgreementRecord>,
+    /// CHECK: escrow for the agreement, if one was created - handler checks it is not in progress
+    #[account(
+        seeds = [b"escrow", agreement.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// anyone can clear an agreement that expired before it went into escrow
+// so the title can be sold again
+#[derive(Accounts)]
+pub struct CloseExpiredAgreement<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // anyone - pays the transaction fee
+    #[account(
+        mut,
+        close = seller_authority,
+        constraint = Clock::get()?.unix_timestamp > agreement.expires_at @ ProtocolError::AgreementNotExpired,
+    )]
+    pub agreement: Account<'info, Agreement>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.key() == agreement.title_deed @ ProtocolError::InvalidTitleDeed,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        mut,
+        seeds = [b"agreement_index", agreement.title_deed.as_ref()],
+        bump = agreement_index.bump,
+        constraint = agreement_index.agreement == agreement.key() @ ProtocolError::InvalidAgreement,
+        close = seller_authority,
+    )]
+    pub agreement_index: Account<'info, AgreementIndex>,
+    // archived copy of the agreement for audit purposes
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgreementRecord::INIT_SPACE,
+        seeds = [b"agreement_record", title_deed.key().as_ref(), title_deed.total_agreements.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agreement_record: Account<'info, AgreementRecord>,
+    /// CHECK: escrow for the agreement, if one was created - handler checks it is not in progress
+    #[account(
+        seeds = [b"escrow", agreement.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+    /// CHECK: seller's wallet - drafted the agreement and receives the rent back
+    #[account(
+        mut,
+        constraint = seller_authority.key() == agreement.seller.authority @ ProtocolError::InvalidSeller,
+    )]
+    pub seller_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
     pub system_program: Program<'info, System>,
 }
 
@@ -304,11 +877,23 @@ pub struct CancelAgreement<'info> {
 pub struct CreateEscrow<'info> {
     #[account(
         mut,
-        constraint = authority.key() == title_deed.authority @ ProtocolError::Unauthorized,
+        constraint = authority.key() == agreement.seller.authority @ ProtocolError::Unauthorized,
     )]
     pub authority: Signer<'info>, // this is the seller(current land owner)
     // title deeed
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // title deed must not already be held in escrow
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::Unauthorized,
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+        // a charged title can only be sold whole, at a price that pays off its lenders
+        constraint = title_deed.charged_amount == 0
+            || (agreement.share_bps == TOTAL_SHARE_BPS && agreement.price >= title_deed.charged_amount)
+            @ ProtocolError::TitleIsCharged,
+    )]
     pub title_deed: Account<'info, TitleDeed>,
     // agreement - must be signed by the buyer
     #[account(
@@ -316,7 +901,18 @@ pub struct CreateEscrow<'info> {
         constraint = agreement.buyer.authority == buyer.authority.key() @ ProtocolError::Unauthorized
     )]
     pub agreement: Account<'info, Agreement>,
+    #[account(
+        constraint = seller.authority == authority.key() @ ProtocolError::Unauthorized,
+        constraint = seller.deceased_at.is_none() @ ProtocolError::OwnerDeceased,
+    )]
     pub seller: Account<'info, User>,
+    /// CHECK: seller's death record, keyed on their wallet - must not exist, whichever user account they deal through
+    #[account(
+        seeds = [b"death_record", authority.key().as_ref()],
+        bump,
+        constraint = death_record.data_is_empty() @ ProtocolError::OwnerDeceased,
+    )]
+    pub death_record: UncheckedAccount<'info>,
     pub buyer: Account<'info, User>,
     #[account(
         init,
@@ -326,6 +922,13 @@ pub struct CreateEscrow<'info> {
         bump
     )]
     pub escrow: Account<'info, Escrow>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
     pub system_program: Program<'info, System>,
 }
 
@@ -357,9 +960,18 @@ pub struct DepositPaymentToEscrow<'info> {
         bump
     )]
     pub deposit: Account<'info, Deposit>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
     pub system_program: Program<'info, System>,
 }
 
+// outstanding charges and their lenders' wallets lead the remaining accounts in pairs, by priority,
+// followed by the wallets of the other co-owners
 #[derive(Accounts)]
 pub struct AuthorizeEscrow<'info> {
     #[account(
@@ -372,31 +984,1155 @@ pub struct AuthorizeEscrow<'info> {
         bump = registrar.bump,
     )]
     pub registrar: Account<'info, Registrar>,
+    // completed escrows are closed so the same parties can deal on the title again
     #[account(
         mut,
         // escrow state must be PaymentDeposited (both title and payment deposited)
         constraint = escrow.state == EscrowState::PaymentDeposited @ ProtocolError::EscrowNotReadyForPayment,
+        constraint = escrow.title_deed == title_deed.key() @ ProtocolError::InvalidTitleDeed,
+        close = seller_authority,
     )]
     pub escrow: Account<'info, Escrow>,
+    // paid out by the handler, the rent left over is returned to the buyer
     #[account(
         mut,
         seeds = [b"deposit", escrow.key().as_ref()],
         bump = deposit.bump,
+        close = buyer_authority,
     )]
     pub deposit: Account<'info, Deposit>,
-    #[account(mut)]
-    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+        // the title must still be held by this escrow
+        constraint = title_deed.authority == escrow.key() @ ProtocolError::TitleAuthorityMismatch,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
     /// CHECK: Ownership history PDA - manually derived and created in handler
     /// PDA seeds: [b"ownership_history", title_deed.key(), (total_transfers + 1).to_le_bytes()]
     #[account(mut)]
     pub ownership_history: UncheckedAccount<'info>,
-    pub title_for_sale: Account<'info, TitleForSale>,
-    pub agreement: Account<'info, Agreement>,
-    pub title_number_lookup: Account<'info, TitleNumberLookup>,
+    // listing is closed once the sale completes so the title can be listed again
+    #[account(
+        mut,
+        seeds = [b"title_for_sale", seller.authority.as_ref(), title_deed.key().as_ref()],
+        bump = title_for_sale.bump,
+        close = seller_authority,
+    )]
+    pub title_for_sale: Box<Account<'info, TitleForSale>>,
+    // completed agreements are archived and closed so the new owner can sell the title
+    #[account(
+        mut,
+        constraint = agreement.key() == escrow.agreement @ ProtocolError::InvalidAgreement,
+        constraint = agreement.title_deed == title_deed.key() @ ProtocolError::InvalidTitleDeed,
+        close = seller_authority,
+    )]
+    pub agreement: Box<Account<'info, Agreement>>,
+    #[account(
+        mut,
+        seeds = [b"agreement_index", title_deed.key().as_ref()],
+        bump = agreement_index.bump,
+        close = seller_authority,
+    )]
+    pub agreement_index: Account<'info, AgreementIndex>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgreementRecord::INIT_SPACE,
+        seeds = [b"agreement_record", title_deed.key().as_ref(), title_deed.total_agreements.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agreement_record: Box<Account<'info, AgreementRecord>>,
+    #[account(
+        seeds = [b"search_certificate", title_deed.key().as_ref(), buyer.authority.as_ref()],
+        bump = search_certificate.bump,
+    )]
+    pub search_certificate: Account<'info, SearchCertificate>,
     pub buyer: Account<'info, User>,
+    // a sale is not completed for a seller who died while it was in escrow
+    #[account(
+        constraint = seller.deceased_at.is_none() @ ProtocolError::OwnerDeceased,
+    )]
     pub seller: Account<'info, User>,
+    /// CHECK: seller's death record, keyed on their wallet - must not exist, whichever user account they deal through
+    #[account(
+        seeds = [b"death_record", seller.authority.as_ref()],
+        bump,
+        constraint = death_record.data_is_empty() @ ProtocolError::OwnerDeceased,
+    )]
+    pub death_record: UncheckedAccount<'info>,
     /// CHECK: Seller's authority account (wallet) - used to receive funds
     #[account(mut)]
     pub seller_authority: AccountInfo<'info>,
+    /// CHECK: buyer's wallet - receives the deposit rent
+    #[account(
+        mut,
+        constraint = buyer_authority.key() == escrow.buyer @ ProtocolError::InvalidBuyer,
+    )]
+    pub buyer_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// buyer or seller backs out of a sale before the registrar authorizes it
+#[derive(Accounts)]
+pub struct CancelEscrow<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // must be the buyer or seller in the escrow
+    // seller paid for the escrow - closing it lets the same parties agree on the title again
+    #[account(
+        mut,
+        seeds = [b"escrow", agreement.key().as_ref()],
+        bump = escrow.bump,
+        close = seller_authority,
+        constraint = escrow.state == EscrowState::TitleDeposited
+            || escrow.state == EscrowState::PaymentDeposited @ ProtocolError::EscrowNotCancellable,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.key() == escrow.title_deed @ ProtocolError::InvalidTitleDeed,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    // seller drafted the agreement and paid for the agreement accounts
+    #[account(
+        mut,
+        close = seller_authority,
+        constraint = agreement.key() == escrow.agreement @ ProtocolError::InvalidAgreement,
+    )]
+    pub agreement: Account<'info, Agreement>,
+    #[account(
+        mut,
+        seeds = [b"agreement_index", title_deed.key().as_ref()],
+        bump = agreement_index.bump,
+        close = seller_authority,
+    )]
+    pub agreement_index: Account<'info, AgreementIndex>,
+    // archived copy of the agreement for audit purposes
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgreementRecord::INIT_SPACE,
+        seeds = [b"agreement_record", title_deed.key().as_ref(), title_deed.total_agreements.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agreement_record: Account<'info, AgreementRecord>,
+    // only present once the buyer has deposited payment - closing it refunds the buyer
+    #[account(
+        mut,
+        seeds = [b"deposit", escrow.key().as_ref()],
+        bump = deposit.bump,
+        close = buyer_authority,
+    )]
+    pub deposit: Option<Account<'info, Deposit>>,
+    /// CHECK: seller's wallet - receives the rent of the agreement accounts
+    #[account(
+        mut,
+        constraint = seller_authority.key() == escrow.seller @ ProtocolError::InvalidSeller,
+    )]
+    pub seller_authority: UncheckedAccount<'info>,
+    /// CHECK: buyer's wallet - receives the refunded deposit
+    #[account(
+        mut,
+        constraint = buyer_authority.key() == escrow.buyer @ ProtocolError::InvalidBuyer,
+    )]
+    pub buyer_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// anyone can unwind an escrow the registrar did not act on before its deadline
+// same accounts as CancelEscrow, only who may unwind the escrow and when differs
+#[derive(Accounts)]
+pub struct ExpireEscrow<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // anyone - pays the transaction fee
+    #[account(
+        mut,
+        seeds = [b"escrow", agreement.key().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.state == EscrowState::TitleDeposited
+            || escrow.state == EscrowState::PaymentDeposited @ ProtocolError::EscrowNotCancellable,
+        constraint = Clock::get()?.unix_timestamp > escrow.deadline @ ProtocolError::EscrowDeadlineNotReached,
+        close = seller_authority,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.key() == escrow.title_deed @ ProtocolError::InvalidTitleDeed,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        mut,
+        close = seller_authority,
+        constraint = agreement.key() == escrow.agreement @ ProtocolError::InvalidAgreement,
+    )]
+    pub agreement: Account<'info, Agreement>,
+    #[account(
+        mut,
+        seeds = [b"agreement_index", title_deed.key().as_ref()],
+        bump = agreement_index.bump,
+        close = seller_authority,
+    )]
+    pub agreement_index: Account<'info, AgreementIndex>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgreementRecord::INIT_SPACE,
+        seeds = [b"agreement_record", title_deed.key().as_ref(), title_deed.total_agreements.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agreement_record: Account<'info, AgreementRecord>,
+    #[account(
+        mut,
+        seeds = [b"deposit", escrow.key().as_ref()],
+        bump = deposit.bump,
+        close = buyer_authority,
+    )]
+    pub deposit: Option<Account<'info, Deposit>>,
+    /// CHECK: seller's wallet - checked against the escrow
+    #[account(
+        mut,
+        constraint = seller_authority.key() == escrow.seller @ ProtocolError::InvalidSeller,
+    )]
+    pub seller_authority: UncheckedAccount<'info>,
+    /// CHECK: buyer's wallet - checked against the escrow
+    #[account(
+        mut,
+        constraint = buyer_authority.key() == escrow.buyer @ ProtocolError::InvalidBuyer,
+    )]
+    pub buyer_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// registrar turns down a sale that failed due diligence, unwinding the escrow
+// same accounts as CancelEscrow, signed by a registrar with jurisdiction over the title
+#[derive(Accounts)]
+pub struct RejectEscrow<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        mut,
+        seeds = [b"escrow", agreement.key().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.state == EscrowState::TitleDeposited
+            || escrow.state == EscrowState::PaymentDeposited @ ProtocolError::EscrowNotRejectable,
+        close = seller_authority,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.key() == escrow.title_deed @ ProtocolError::InvalidTitleDeed,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        mut,
+        close = seller_authority,
+        constraint = agreement.key() == escrow.agreement @ ProtocolError::InvalidAgreement,
+    )]
+    pub agreement: Account<'info, Agreement>,
+    #[account(
+        mut,
+        seeds = [b"agreement_index", title_deed.key().as_ref()],
+        bump = agreement_index.bump,
+        close = seller_authority,
+    )]
+    pub agreement_index: Account<'info, AgreementIndex>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgreementRecord::INIT_SPACE,
+        seeds = [b"agreement_record", title_deed.key().as_ref(), title_deed.total_agreements.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agreement_record: Account<'info, AgreementRecord>,
+    #[account(
+        mut,
+        seeds = [b"deposit", escrow.key().as_ref()],
+        bump = deposit.bump,
+        close = buyer_authority,
+    )]
+    pub deposit: Option<Account<'info, Deposit>>,
+    /// CHECK: seller's wallet - checked against the escrow
+    #[account(
+        mut,
+        constraint = seller_authority.key() == escrow.seller @ ProtocolError::InvalidSeller,
+    )]
+    pub seller_authority: UncheckedAccount<'info>,
+    /// CHECK: buyer's wallet - checked against the escrow
+    #[account(
+        mut,
+        constraint = buyer_authority.key() == escrow.buyer @ ProtocolError::InvalidBuyer,
+    )]
+    pub buyer_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// owner offers their title deed to another user at no cost
+#[derive(Accounts)]
+pub struct InitiateGiftTransfer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // must be an owner of the title deed
+    #[account(
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // title deed must be owned by the authority and not held in escrow
+        constraint = title_deed.co_owners.iter().any(|co_owner| co_owner.authority == authority.key()) @ ProtocolError::Unauthorized,
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::Unauthorized,
+        constraint = !title_deed.is_for_sale @ ProtocolError::TitleIsForSale,
+        constraint = title_deed.listed_shares == 0 @ ProtocolError::TitleIsForSale,
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        constraint = donor.authority == authority.key() @ ProtocolError::Unauthorized,
+        // a deceased owner's titles pass through succession instead
+        constraint = donor.deceased_at.is_none() @ ProtocolError::OwnerDeceased,
+    )]
+    pub donor: Account<'info, User>,
+    /// CHECK: donor's death record, keyed on their wallet - must not exist, whichever user account they deal through
+    #[account(
+        seeds = [b"death_record", authority.key().as_ref()],
+        bump,
+        constraint = death_record.data_is_empty() @ ProtocolError::OwnerDeceased,
+    )]
+    pub death_record: UncheckedAccount<'info>,
+    #[account(
+        constraint = recipient.authority != authority.key() @ ProtocolError::InvalidGiftRecipient,
+    )]
+    pub recipient: Account<'info, User>,
+    // one pending gift per title deed
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GiftTransfer::INIT_SPACE,
+        seeds = [b"gift_transfer", title_deed.key().as_ref()],
+        bump
+    )]
+    pub gift_transfer: Account<'info, GiftTransfer>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptGiftTransfer<'info> {
+    pub authority: Signer<'info>, // must be the recipient of the gift
+    #[account(
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        mut,
+        seeds = [b"gift_transfer", title_deed.key().as_ref()],
+        bump = gift_transfer.bump,
+        constraint = gift_transfer.recipient.authority == authority.key() @ ProtocolError::Unauthorized,
+        constraint = gift_transfer.accepted_at.is_none() @ ProtocolError::GiftAlreadyAccepted,
+    )]
+    pub gift_transfer: Account<'info, GiftTransfer>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+// registrar approves an accepted gift, transferring the title deed to the recipient
+#[derive(Accounts)]
+pub struct ApproveGiftTransfer<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // title deed must not have been listed or taken to escrow since - the donor's holding is checked on transfer
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+        constraint = !title_deed.is_for_sale @ ProtocolError::TitleIsForSale,
+        constraint = title_deed.listed_shares == 0 @ ProtocolError::TitleIsForSale,
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        mut,
+        seeds = [b"gift_transfer", title_deed.key().as_ref()],
+        bump = gift_transfer.bump,
+        close = donor_authority,
+        constraint = gift_transfer.accepted_at.is_some() @ ProtocolError::GiftNotAccepted,
+    )]
+    pub gift_transfer: Box<Account<'info, GiftTransfer>>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OwnershipHistory::INIT_SPACE,
+        seeds = [
+            b"ownership_history",
+            title_deed.key().as_ref(),
+            title_deed.total_transfers
+                .checked_add(1)
+                .ok_or(ProtocolError::ArithmeticOverflow)?
+                .to_le_bytes()
+                .as_ref()
+        ],
+        bump
+    )]
+    pub ownership_history: Account<'info, OwnershipHistory>,
+    /// CHECK: donor's wallet - receives the rent of the gift transfer account
+    #[account(
+        mut,
+        constraint = donor_authority.key() == gift_transfer.donor @ ProtocolError::Unauthorized,
+    )]
+    pub donor_authority: UncheckedAccount<'info>,
+    // a gift is not completed for a donor who died before it was approved
+    #[account(
+        constraint = donor.authority == gift_transfer.donor @ ProtocolError::Unauthorized,
+        constraint = donor.deceased_at.is_none() @ ProtocolError::OwnerDeceased,
+    )]
+    pub donor: Account<'info, User>,
+    /// CHECK: donor's death record, keyed on their wallet - must not exist, whichever user account they deal through
+    #[account(
+        seeds = [b"death_record", gift_transfer.donor.as_ref()],
+        bump,
+        constraint = death_record.data_is_empty() @ ProtocolError::OwnerDeceased,
+    )]
+    pub death_record: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// donor or recipient withdraws a pending gift
+#[derive(Accounts)]
+pub struct CancelGiftTransfer<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        mut,
+        seeds = [b"gift_transfer", title_deed.key().as_ref()],
+        bump = gift_transfer.bump,
+        close = donor_authority,
+        constraint = authority.key() == gift_transfer.donor
+            || authority.key() == gift_transfer.recipient.authority @ ProtocolError::Unauthorized,
+    )]
+    pub gift_transfer: Account<'info, GiftTransfer>,
+    /// CHECK: donor's wallet - receives the rent of the gift transfer account
+    #[account(
+        mut,
+        constraint = donor_authority.key() == gift_transfer.donor @ ProtocolError::Unauthorized,
+    )]
+    pub donor_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+// registrar records an owner as deceased from a court grant, freezing the title and the rest of their dealings.
+// on a title held in common only the deceased's share passes to the beneficiaries
+#[derive(Accounts)]
+pub struct RecordSuccession<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // an escrow must be resolved before the title can pass to the estate
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+        // a joint tenant's share passes to the survivors through record_survivorship instead
+        constraint = title_deed.tenure != Tenure::JointTenancy @ ProtocolError::TitleIsJointTenancy,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    // marked deceased so none of their other titles can be dealt with either
+    #[account(
+        mut,
+        constraint = title_deed.co_owners.iter().any(|co_owner| co_owner.authority == deceased.authority) @ ProtocolError::Unauthorized,
+    )]
+    pub deceased: Account<'info, User>,
+    /// CHECK: created by the handler unless an earlier title already recorded the death
+    #[account(
+        mut,
+        seeds = [b"death_record", deceased.authority.as_ref()],
+        bump
+    )]
+    pub death_record: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Succession::INIT_SPACE,
+        seeds = [b"succession", title_deed.key().as_ref()],
+        bump
+    )]
+    pub succession: Account<'info, Succession>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// registrar transfers a share of a deceased owner's title to a beneficiary, with the administrator's consent.
+// the succession is closed once the whole title has passed to the beneficiaries
+#[derive(Accounts)]
+pub struct TransferToBeneficiary<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        constraint = administrator.key() == succession.administrator @ ProtocolError::Unauthorized,
+    )]
+    pub administrator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        mut,
+        seeds = [b"succession", title_deed.key().as_ref()],
+        bump = succession.bump,
+    )]
+    pub succession: Box<Account<'info, Succession>>,
+    #[account(
+        constraint = succession.beneficiaries.contains(&beneficiary.authority) @ ProtocolError::InvalidBeneficiary,
+    )]
+    pub beneficiary: Account<'info, User>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OwnershipHistory::INIT_SPACE,
+        seeds = [
+            b"ownership_history",
+            title_deed.key().as_ref(),
+            title_deed.total_transfers
+                .checked_add(1)
+                .ok_or(ProtocolError::ArithmeticOverflow)?
+                .to_le_bytes()
+                .as_ref()
+        ],
+        bump
+    )]
+    pub ownership_history: Account<'info, OwnershipHistory>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// registrar records a joint tenant's death, passing their share to the surviving joint tenants.
+// the survivor becomes the registered owner if the deceased was
+#[derive(Accounts)]
+pub struct RecordSurvivorship<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // an escrow must be resolved before the title can pass to the survivors
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+        constraint = title_deed.tenure == Tenure::JointTenancy @ ProtocolError::TitleNotJointTenancy,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    // marked deceased so none of their other titles can be dealt with either
+    #[account(
+        mut,
+        constraint = title_deed.co_owners.iter().any(|co_owner| co_owner.authority == deceased.authority) @ ProtocolError::Unauthorized,
+    )]
+    pub deceased: Account<'info, User>,
+    /// CHECK: created by the handler unless an earlier title already recorded the death
+    #[account(
+        mut,
+        seeds = [b"death_record", deceased.authority.as_ref()],
+        bump
+    )]
+    pub death_record: UncheckedAccount<'info>,
+    #[account(
+        constraint = survivor.authority != deceased.authority @ ProtocolError::InvalidSurvivor,
+        constraint = title_deed.co_owners.iter().any(|co_owner| co_owner.authority == survivor.authority) @ ProtocolError::InvalidSurvivor,
+    )]
+    pub survivor: Account<'info, User>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OwnershipHistory::INIT_SPACE,
+        seeds = [
+            b"ownership_history",
+            title_deed.key().as_ref(),
+            title_deed.total_transfers
+                .checked_add(1)
+                .ok_or(ProtocolError::ArithmeticOverflow)?
+                .to_le_bytes()
+                .as_ref()
+        ],
+        bump
+    )]
+    pub ownership_history: Account<'info, OwnershipHistory>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// registrar records the co-owners of a title with the registered owner's consent
+#[derive(Accounts)]
+pub struct RegisterCoOwnership<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        constraint = owner.key() == title_deed.owner.authority @ ProtocolError::Unauthorized,
+    )]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // ownership cannot change while the title is being dealt with
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+        constraint = !title_deed.is_for_sale @ ProtocolError::TitleIsForSale,
+        constraint = title_deed.listed_shares == 0 @ ProtocolError::TitleIsForSale,
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+    )]
+    pub title_deed: Account<'info, TitleDeed>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+// registrar retires a title deed and registers its parcels as new titles, with the co-owners' consent.
+// each parcel's title deed, title number lookup and first ownership history entry lead the remaining accounts,
+// followed by the other co-owners as signers
+#[derive(Accounts)]
+pub struct SubdivideTitleDeed<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        constraint = owner.key() == title_deed.owner.authority @ ProtocolError::Unauthorized,
+    )]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.retired_at.is_none() @ ProtocolError::TitleRetired,
+        // the title cannot be subdivided while it is being dealt with
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+        constraint = !title_deed.is_for_sale @ ProtocolError::TitleIsForSale,
+        constraint = title_deed.listed_shares == 0 @ ProtocolError::TitleIsForSale,
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+        constraint = title_deed.charged_amount == 0 @ ProtocolError::TitleIsCharged,
+        // easements are registered against the whole title, not its parcels
+        constraint = title_deed.easements.is_empty() @ ProtocolError::TitleHasEasements,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// registrar merges titles held by the same owners into a new title, with the co-owners' consent.
+// the source title deeds follow in the remaining accounts, along with the other co-owners as signers
+#[derive(Accounts)]
+#[instruction(title_number: String)]
+pub struct AmalgamateTitleDeeds<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TitleDeed::INIT_SPACE,
+        seeds = [b"title_deed", title_number.as_bytes()],
+        bump
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TitleNumberLookup::INIT_SPACE,
+        seeds = [b"title_number_lookup", title_number.as_bytes()],
+        bump
+    )]
+    pub title_number_lookup: Account<'info, TitleNumberLookup>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OwnershipHistory::INIT_SPACE,
+        seeds = [b"ownership_history", title_deed.key().as_ref(), 0u64.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub ownership_history: Box<Account<'info, OwnershipHistory>>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// registrar registers a lender's charge against a title with the co-owners' consent.
+// the other co-owners co-sign through the remaining accounts
+#[derive(Accounts)]
+pub struct RegisterCharge<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    pub lender: Signer<'info>,
+    #[account(
+        constraint = owner.key() == title_deed.owner.authority @ ProtocolError::Unauthorized,
+    )]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // a title held in escrow or retired cannot be charged
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Charge::INIT_SPACE,
+        seeds = [b"charge", title_deed.key().as_ref(), (title_deed.total_charges + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub charge: Account<'info, Charge>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// registrar discharges a charge once the lender releases it
+#[derive(Accounts)]
+pub struct DischargeCharge<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        constraint = lender.key() == charge.lender @ ProtocolError::Unauthorized,
+    )]
+    pub lender: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        mut,
+        seeds = [b"charge", title_deed.key().as_ref(), charge.priority.to_le_bytes().as_ref()],
+        bump = charge.bump,
+        constraint = charge.discharged_at.is_none() @ ProtocolError::ChargeAlreadyDischarged,
+    )]
+    pub charge: Account<'info, Charge>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+// registrar lodges a caveat or court order against a title on behalf of the caveator
+#[derive(Accounts)]
+pub struct LodgeCaveat<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    pub caveator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        constraint = title_deed.retired_at.is_none() @ ProtocolError::TitleRetired,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Caveat::INIT_SPACE,
+        seeds = [b"caveat", title_deed.key().as_ref(), (title_deed.total_caveats + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub caveat: Account<'info, Caveat>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// caveator withdraws their own caveat - court orders can only be removed by a registrar
+#[derive(Accounts)]
+pub struct WithdrawCaveat<'info> {
+    #[account(
+        constraint = authority.key() == caveat.caveator @ ProtocolError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        mut,
+        seeds = [b"caveat", title_deed.key().as_ref(), caveat.sequence_number.to_le_bytes().as_ref()],
+        bump = caveat.bump,
+        constraint = caveat.status == CaveatStatus::Lodged @ ProtocolError::CaveatNotLodged,
+        constraint = caveat.kind == CaveatKind::Caveat @ ProtocolError::CaveatIsCourtOrder,
+    )]
+    pub caveat: Account<'info, Caveat>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+// registrar removes a caveat or court order, e.g. on a further order of the court
+#[derive(Accounts)]
+pub struct RemoveCaveat<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        mut,
+        seeds = [b"caveat", title_deed.key().as_ref(), caveat.sequence_number.to_le_bytes().as_ref()],
+        bump = caveat.bump,
+        constraint = caveat.status == CaveatStatus::Lodged @ ProtocolError::CaveatNotLodged,
+    )]
+    pub caveat: Account<'info, Caveat>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+// anyone can lift a caveat once it has expired
+#[derive(Accounts)]
+pub struct LapseCaveat<'info> {
+    pub authority: Signer<'info>, // anyone
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        mut,
+        seeds = [b"caveat", title_deed.key().as_ref(), caveat.sequence_number.to_le_bytes().as_ref()],
+        bump = caveat.bump,
+        constraint = caveat.status == CaveatStatus::Lodged @ ProtocolError::CaveatNotLodged,
+    )]
+    pub caveat: Account<'info, Caveat>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+// registrar registers a lease granted by the owner - every co-owner co-signs through the remaining accounts
+#[derive(Accounts)]
+pub struct RegisterLease<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        constraint = owner.key() == title_deed.owner.authority @ ProtocolError::Unauthorized,
+    )]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", title_deed.title_number.as_bytes()],
+        bump = title_deed.bump,
+        // a title held in escrow or retired cannot be leased
+        constraint = title_deed.authority == title_deed.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+        constraint = !title_deed.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = title_deed.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+    )]
+    pub title_deed: Box<Account<'info, TitleDeed>>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Lease::INIT_SPACE,
+        seeds = [b"lease", title_deed.key().as_ref(), (title_deed.total_leases + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lease: Account<'info, Lease>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub system_program: Program<'info, System>,
+}
+
+// registrar registers an easement with the consent of both owners - co-owners of either title
+// co-sign through the remaining accounts
+#[derive(Accounts)]
+pub struct RegisterEasement<'info> {
+    #[account(
+        mut,
+        // must be registrar
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        constraint = dominant_owner.key() == dominant_title.owner.authority @ ProtocolError::Unauthorized,
+    )]
+    pub dominant_owner: Signer<'info>,
+    #[account(
+        constraint = servient_owner.key() == servient_title.owner.authority @ ProtocolError::Unauthorized,
+    )]
+    pub servient_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", dominant_title.title_number.as_bytes()],
+        bump = dominant_title.bump,
+        // neither title can be held in escrow, retired or frozen
+        constraint = dominant_title.authority == dominant_title.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+        constraint = !dominant_title.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = dominant_title.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+        constraint = dominant_title.key() != servient_title.key() @ ProtocolError::InvalidEasement,
+    )]
+    pub dominant_title: Box<Account<'info, TitleDeed>>,
+    #[account(
+        mut,
+        seeds = [b"title_deed", servient_title.title_number.as_bytes()],
+        bump = servient_title.bump,
+        constraint = servient_title.authority == servient_title.owner.authority @ ProtocolError::TitleAuthorityMismatch,
+        constraint = !servient_title.in_succession @ ProtocolError::TitleInSuccession,
+        constraint = servient_title.active_caveats == 0 @ ProtocolError::TitleHasCaveat,
+    )]
+    pub servient_title: Box<Account<'info, TitleDeed>>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Easement::INIT_SPACE,
+        seeds = [b"easement", servient_title.key().as_ref(), (servient_title.total_easements + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub easement: Account<'info, Easement>,
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        // no dealings on the registry while the protocol is paused
+        constraint = !protocol_state.is_paused @ ProtocolError::ProtocolPaused
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
     pub system_program: Program<'info, System>,
 }
@@ -48,4 +48,146 @@ pub enum ProtocolError {
     ArithmeticOverflow,
     #[msg("Arithmetic underflow")]
     ArithmeticUnderflow,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Protocol is already paused")]
+    ProtocolAlreadyPaused,
+    #[msg("Protocol is not paused")]
+    ProtocolNotPaused,
+    #[msg("Admin already exists")]
+    AdminAlreadyExists,
+    #[msg("Maximum number of admins reached")]
+    AdminLimitReached,
+    #[msg("Cannot remove the last admin")]
+    CannotRemoveLastAdmin,
+    #[msg("Proposal already approved by this admin")]
+    ProposalAlreadyApproved,
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal has not reached the approval threshold")]
+    ProposalThresholdNotReached,
+    #[msg("Proposal action does not match the instruction")]
+    ProposalActionMismatch,
+    #[msg("Approval threshold must be between 1 and the number of admins")]
+    InvalidApprovalThreshold,
+    #[msg("Registrar is suspended")]
+    RegistrarSuspended,
+    #[msg("Registrar has been revoked")]
+    RegistrarRevoked,
+    #[msg("Registrar is not suspended")]
+    RegistrarNotSuspended,
+    #[msg("Registrar must be assigned between 1 and 5 districts")]
+    InvalidRegistrarDistricts,
+    #[msg("Title deed is outside the registrar's jurisdiction")]
+    OutsideRegistrarJurisdiction,
+    #[msg("Search validity period must be at least one day")]
+    InvalidSearchValidityPeriod,
+    #[msg("Search certificate has expired")]
+    SearchCertificateExpired,
+    #[msg("Title has changed since the search certificate was issued")]
+    SearchCertificateStale,
+    #[msg("Agreement is in escrow - cancel the escrow instead")]
+    AgreementInEscrow,
+    #[msg("Escrow can only be cancelled before it is completed")]
+    EscrowNotCancellable,
+    #[msg("Deposit account is required to refund the buyer")]
+    MissingDeposit,
+    #[msg("Escrow period must be at least one day")]
+    InvalidEscrowPeriod,
+    #[msg("Escrow deadline has passed")]
+    EscrowExpired,
+    #[msg("Escrow deadline has not passed yet")]
+    EscrowDeadlineNotReached,
+    #[msg("Agreement validity period must be at least one day")]
+    InvalidAgreementValidityPeriod,
+    #[msg("Agreement has expired")]
+    AgreementExpired,
+    #[msg("Agreement has not expired yet")]
+    AgreementNotExpired,
+    #[msg("Reason must be at most 200 characters")]
+    ReasonTooLong,
+    #[msg("Escrow can only be rejected while awaiting authorization")]
+    EscrowNotRejectable,
+    #[msg("Title deed is listed for sale")]
+    TitleIsForSale,
+    #[msg("A title deed cannot be gifted to its current owner")]
+    InvalidGiftRecipient,
+    #[msg("Gift has already been accepted")]
+    GiftAlreadyAccepted,
+    #[msg("Gift has not been accepted by the recipient")]
+    GiftNotAccepted,
+    #[msg("Title deed is under succession")]
+    TitleInSuccession,
+    #[msg("Court grant reference must be between 1 and 50 characters")]
+    InvalidCourtGrantReference,
+    #[msg("Succession must name between 1 and 5 beneficiaries")]
+    InvalidBeneficiaries,
+    #[msg("Beneficiary is not named in the succession")]
+    InvalidBeneficiary,
+    #[msg("Co-owners must be between 1 and 5 distinct users including the registered owner")]
+    InvalidCoOwners,
+    #[msg("Co-owner shares must total 10000 basis points, held equally by joint tenants")]
+    InvalidCoOwnerShares,
+    #[msg("All co-owners must sign")]
+    MissingCoOwnerSignature,
+    #[msg("Co-owner wallet is required to receive sale proceeds")]
+    MissingCoOwnerAccount,
+    #[msg("Not supported on co-owned title deeds")]
+    TitleIsCoOwned,
+    #[msg("Share must be more than zero and no more than the owner holds")]
+    InvalidShare,
+    #[msg("Title deed has been retired")]
+    TitleRetired,
+    #[msg("A title must be subdivided into between 2 and 5 parcels")]
+    InvalidParcels,
+    #[msg("Parcel acreages must be positive and add up to the parent title's acreage")]
+    InvalidParcelAcreage,
+    #[msg("Parcel accounts do not match the parcel title numbers")]
+    InvalidParcelAccount,
+    #[msg("Between 2 and 5 distinct titles in the same district can be amalgamated")]
+    InvalidAmalgamation,
+    #[msg("Amalgamated titles must be held by the same owners on the same terms")]
+    AmalgamationOwnerMismatch,
+    #[msg("Title deed has outstanding charges")]
+    TitleIsCharged,
+    #[msg("Charge amount must be more than zero")]
+    InvalidChargeAmount,
+    #[msg("Charge has already been discharged")]
+    ChargeAlreadyDischarged,
+    #[msg("Every outstanding charge and its lender's wallet is required to pay off the charges")]
+    MissingChargeAccount,
+    #[msg("Title deed has a caveat lodged against it")]
+    TitleHasCaveat,
+    #[msg("Caveat expiry must be in the future")]
+    InvalidCaveatExpiry,
+    #[msg("Caveat is no longer lodged")]
+    CaveatNotLodged,
+    #[msg("Court orders can only be removed by a registrar")]
+    CaveatIsCourtOrder,
+    #[msg("Caveat has not expired")]
+    CaveatNotExpired,
+    #[msg("Lease must end after it starts and state its permitted use")]
+    InvalidLeaseTerms,
+    #[msg("Title deed has the maximum number of leases in force")]
+    TooManyLeases,
+    #[msg("Easement must link two different titles and describe the right granted")]
+    InvalidEasement,
+    #[msg("Title deed has the maximum number of easements")]
+    TooManyEasements,
+    #[msg("Title deed has a lease in force")]
+    TitleIsLeased,
+    #[msg("Title deed has easements registered against it")]
+    TitleHasEasements,
+    #[msg("Owner has been recorded as deceased")]
+    OwnerDeceased,
+    #[msg("A joint tenant's share passes to the surviving joint tenants")]
+    TitleIsJointTenancy,
+    #[msg("Survivorship only applies to titles held in joint tenancy")]
+    TitleNotJointTenancy,
+    #[msg("Survivor must be one of the surviving joint tenants")]
+    InvalidSurvivor,
+    #[msg("Admins must be distinct")]
+    DuplicateAdmin,
+    #[msg("Proposal has expired")]
+    ProposalExpired,
 }
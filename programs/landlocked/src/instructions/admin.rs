@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    close_program_account, error::ProtocolError, execute_proposal, is_admin, state::ProposalAction, AddAdmin, RemoveAdmin,
+    RotateAdmin, MAX_ADMINS,
+};
+
+// Adds an admin to the protocol once the proposal is approved
+// the new admin then creates their admin account via confirm_admin_account
+pub fn add_admin_handler(ctx: Context<AddAdmin>) -> Result<()> {
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    let ProposalAction::AddAdmin { admin } = ctx.accounts.proposal.action else {
+        return err!(ProtocolError::ProposalActionMismatch);
+    };
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    require!(
+        !protocol_state.admins.contains(&admin),
+        ProtocolError::AdminAlreadyExists
+    );
+    require!(
+        protocol_state.admins.len() < MAX_ADMINS,
+        ProtocolError::AdminLimitReached
+    );
+    protocol_state.admins.push(admin);
+
+    msg!("Admin {} added to the protocol", admin);
+    Ok(())
+}
+
+// Removes an admin once the proposal is approved
+// their admin account (if confirmed) is closed and the rent returned to them
+pub fn remove_admin_handler(ctx: Context<RemoveAdmin>) -> Result<()> {
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    let ProposalAction::RemoveAdmin { admin } = ctx.accounts.proposal.action else {
+        return err!(ProtocolError::ProposalActionMismatch);
+    };
+    require!(
+        ctx.accounts.former_admin.key() == admin,
+        ProtocolError::InvalidAdmin
+    );
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    let position = protocol_state
+        .admins
+        .iter()
+        .position(|a| *a == admin)
+        .ok_or(ProtocolError::InvalidAdmin)?;
+    require!(
+        protocol_state.admins.len() > 1,
+        ProtocolError::CannotRemoveLastAdmin
+    );
+    // the remaining admins must still be able to reach the approval threshold
+    require!(
+        protocol_state.admins.len() > protocol_state.config.approval_threshold as usize,
+        ProtocolError::InvalidApprovalThreshold
+    );
+    protocol_state.admins.remove(position);
+
+    close_program_account(
+        &ctx.accounts.former_admin_account.to_account_info(),
+        &ctx.accounts.former_admin.to_account_info(),
+        ctx.program_id,
+    )?;
+
+    msg!("Admin {} removed from the protocol", admin);
+    Ok(())
+}
+
+// Replaces an admin's key (e.g. lost or compromised wallet) once the proposal is approved
+// the old admin account (if confirmed) is closed and the new admin confirms their own account
+pub fn rotate_admin_handler(ctx: Context<RotateAdmin>) -> Result<()> {
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    let ProposalAction::RotateAdmin { old_admin, new_admin } = ctx.accounts.proposal.action else {
+        return err!(ProtocolError::ProposalActionMismatch);
+    };
+    require!(
+        ctx.accounts.former_admin.key() == old_admin,
+        ProtocolError::InvalidAdmin
+    );
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    require!(
+        !protocol_state.admins.contains(&new_admin),
+        ProtocolError::AdminAlreadyExists
+    );
+    let position = protocol_state
+        .admins
+        .iter()
+        .position(|a| *a == old_admin)
+        .ok_or(ProtocolError::InvalidAdmin)?;
+    protocol_state.admins[position] = new_admin;
+
+    close_program_account(
+        &ctx.accounts.former_admin_account.to_account_info(),
+        &ctx.accounts.former_admin.to_account_info(),
+        ctx.program_id,
+    )?;
+
+    msg!("Admin {} rotated to {}", old_admin, new_admin);
+    Ok(())
+}
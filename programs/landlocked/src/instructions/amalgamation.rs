@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    contexts::AmalgamateTitleDeeds,
+    error::ProtocolError,
+    state::{OwnershipHistory, TitleDeed, TransferType},
+    in_registrar_jurisdiction, is_registrar, require_co_owner_signatures, MAX_AMALGAMATED_TITLES,
+    MAX_TITLE_NUMBER_LENGTH, TOTAL_SHARE_BPS,
+};
+
+// source titles are written back from the remaining accounts, so they share the lifetime of the instruction's accounts
+pub fn amalgamate_title_deeds_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AmalgamateTitleDeeds<'info>>,
+    title_number: String,
+) -> Result<()> {
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+
+    require!(
+        !title_number.is_empty() && title_number.len() <= MAX_TITLE_NUMBER_LENGTH,
+        ProtocolError::InvalidTitleDeedDetails
+    );
+    // co-owners co-sign alongside the source title deeds, which can never sign
+    let source_accounts: Vec<&'info AccountInfo<'info>> = ctx
+        .remaining_accounts
+        .iter()
+        .filter(|account| !account.is_signer)
+        .collect();
+    require!(
+        source_accounts.len() >= 2 && source_accounts.len() <= MAX_AMALGAMATED_TITLES,
+        ProtocolError::InvalidAmalgamation
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut sources: Vec<Account<'info, TitleDeed>> = Vec::with_capacity(source_accounts.len());
+    for account in source_accounts {
+        require!(
+            !sources.iter().any(|source| source.key() == account.key()),
+            ProtocolError::InvalidAmalgamation
+        );
+        let source = Account::<TitleDeed>::try_from(account)?;
+
+        require!(source.retired_at.is_none(), ProtocolError::TitleRetired);
+        // a source cannot be merged away while it is being dealt with
+        require!(
+            source.authority == source.owner.authority,
+            ProtocolError::TitleAuthorityMismatch
+        );
+        require!(
+            !source.is_for_sale && source.listed_shares == 0,
+            ProtocolError::TitleIsForSale
+        );
+        require!(!source.in_succession, ProtocolError::TitleInSuccession);
+        require!(source.charged_amount == 0, ProtocolError::TitleIsCharged);
+        require!(source.active_caveats == 0, ProtocolError::TitleHasCaveat);
+        require!(
+            source.leases.iter().all(|lease| lease.term_end <= now),
+            ProtocolError::TitleIsLeased
+        );
+        require!(source.easements.is_empty(), ProtocolError::TitleHasEasements);
+
+        require!(
+            source.owner.authority == ctx.accounts.owner.key(),
+            ProtocolError::Unauthorized
+        );
+        if let Some(first) = sources.first() {
+            require!(
+                source.district_land_registry == first.district_land_registry,
+                ProtocolError::InvalidAmalgamation
+            );
+            require!(
+                source.tenure == first.tenure && source.co_owners == first.co_owners,
+                ProtocolError::AmalgamationOwnerMismatch
+            );
+        } else {
+            // registrar can only amalgamate titles in their own district land registries
+            in_registrar_jurisdiction(&registrar, &source.district_land_registry)?;
+        }
+        sources.push(source);
+    }
+
+    // every co-owner has to consent to their titles being merged
+    require_co_owner_signatures(&sources[0], &ctx.accounts.owner.key(), ctx.remaining_accounts)?;
+
+    let acreage: f64 = sources.iter().map(|source| source.acreage).sum();
+    let parent_titles: Vec<Pubkey> = sources.iter().map(|source| source.key()).collect();
+    let title_deed_key = ctx.accounts.title_deed.key();
+    let first = &sources[0];
+
+    // the merged title is held by the sources' owners on the same terms
+    ctx.accounts.title_deed.set_inner(TitleDeed {
+        owner: first.owner.clone(),
+        authority: first.owner.authority,
+        title_number: title_number.clone(),
+        location: first.location.clone(),
+        acreage,
+        district_land_registry: first.district_land_registry.clone(),
+        registration_date: now,
+        registry_mapsheet_number: first.registry_mapsheet_number,
+        is_for_sale: false,
+        listed_shares: 0,
+        total_transfers: 0,
+        total_agreements: 0,
+        in_succession: false,
+        tenure: first.tenure,
+        co_owners: first.co_owners.clone(),
+        retired_at: None,
+        parent_titles: parent_titles.clone(),
+        child_titles: Vec::new(),
+        total_charges: 0,
+        charged_amount: 0,
+        total_caveats: 0,
+        active_caveats: 0,
+        total_leases: 0,
+        leases: Vec::new(),
+        total_easements: 0,
+        easements: Vec::new(),
+        bump: ctx.bumps.title_deed,
+    });
+
+    ctx.accounts.ownership_history.set_inner(OwnershipHistory {
+        title_deed: title_deed_key,
+        previous_owner: first.owner.authority,
+        current_owner: first.owner.authority,
+        transferred_at: now,
+        transfer_type: TransferType::Amalgamation,
+        share_bps: TOTAL_SHARE_BPS,
+        co_owners: first.co_owners.clone(),
+        parent_titles,
+        sequence_number: 0,
+        bump: ctx.bumps.ownership_history,
+    });
+
+    let title_number_lookup = &mut ctx.accounts.title_number_lookup;
+    title_number_lookup.title_number = title_number;
+    title_number_lookup.title_deed = title_deed_key;
+    title_number_lookup.bump = ctx.bumps.title_number_lookup;
+
+    // retired sources keep their owners for the record but can no longer be dealt with
+    for source in sources.iter_mut() {
+        source.authority = Pubkey::default();
+        source.retired_at = Some(now);
+        source.child_titles = vec![title_deed_key];
+        source.exit(ctx.program_id)?;
+    }
+
+    msg!(
+        "{} title deeds retired and amalgamated into title deed {} ({} acres)",
+        sources.len(),
+        ctx.accounts.title_deed.title_number,
+        acreage
+    );
+    Ok(())
+}
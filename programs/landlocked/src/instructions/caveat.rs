@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    contexts::{LapseCaveat, LodgeCaveat, RemoveCaveat, WithdrawCaveat},
+    error::ProtocolError,
+    state::{Caveat, CaveatKind, CaveatStatus, TitleDeed},
+    in_registrar_jurisdiction, is_registrar, MAX_REASON_LENGTH,
+};
+
+pub fn lodge_caveat_handler(
+    ctx: Context<LodgeCaveat>,
+    kind: CaveatKind,
+    reason: String,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+
+    require!(reason.len() <= MAX_REASON_LENGTH, ProtocolError::ReasonTooLong);
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        expires_at.is_none_or(|expires_at| expires_at > now),
+        ProtocolError::InvalidCaveatExpiry
+    );
+
+    // caveats are numbered in the order they are lodged, matching the caveat seeds
+    let title_deed = &mut ctx.accounts.title_deed;
+    title_deed.total_caveats = title_deed.total_caveats
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+    title_deed.active_caveats = title_deed.active_caveats
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+
+    let caveat = &mut ctx.accounts.caveat;
+    caveat.title_deed = title_deed.key();
+    caveat.caveator = ctx.accounts.caveator.key();
+    caveat.kind = kind;
+    caveat.reason = reason;
+    caveat.lodged_by = ctx.accounts.authority.key();
+    caveat.lodged_at = now;
+    caveat.expires_at = expires_at;
+    caveat.status = CaveatStatus::Lodged;
+    caveat.lifted_by = None;
+    caveat.lifted_at = None;
+    caveat.lifting_reason = String::new();
+    caveat.sequence_number = title_deed.total_caveats;
+    caveat.bump = ctx.bumps.caveat;
+
+    msg!(
+        "Caveat {} lodged against title deed {} by {}",
+        caveat.sequence_number,
+        title_deed.title_number,
+        caveat.caveator
+    );
+    Ok(())
+}
+
+pub fn withdraw_caveat_handler(ctx: Context<WithdrawCaveat>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    lift_caveat(
+        &mut ctx.accounts.title_deed,
+        &mut ctx.accounts.caveat,
+        CaveatStatus::Withdrawn,
+        authority,
+        String::new(),
+    )
+}
+
+pub fn remove_caveat_handler(ctx: Context<RemoveCaveat>, reason: String) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+    require!(reason.len() <= MAX_REASON_LENGTH, ProtocolError::ReasonTooLong);
+
+    let authority = ctx.accounts.authority.key();
+    lift_caveat(
+        &mut ctx.accounts.title_deed,
+        &mut ctx.accounts.caveat,
+        CaveatStatus::Removed,
+        authority,
+        reason,
+    )
+}
+
+pub fn lapse_caveat_handler(ctx: Context<LapseCaveat>) -> Result<()> {
+    // caveats without an expiry stay until withdrawn or removed
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.caveat.expires_at.is_some_and(|expires_at| now > expires_at),
+        ProtocolError::CaveatNotExpired
+    );
+
+    let authority = ctx.accounts.authority.key();
+    lift_caveat(
+        &mut ctx.accounts.title_deed,
+        &mut ctx.accounts.caveat,
+        CaveatStatus::Lapsed,
+        authority,
+        String::new(),
+    )
+}
+
+// records who lifted the caveat and why, releasing its hold on the title
+fn lift_caveat(
+    title_deed: &mut TitleDeed,
+    caveat: &mut Caveat,
+    status: CaveatStatus,
+    lifted_by: Pubkey,
+    reason: String,
+) -> Result<()> {
+    title_deed.active_caveats = title_deed.active_caveats
+        .checked_sub(1)
+        .ok_or(ProtocolError::ArithmeticUnderflow)?;
+
+    caveat.status = status;
+    caveat.lifted_by = Some(lifted_by);
+    caveat.lifted_at = Some(Clock::get()?.unix_timestamp);
+    caveat.lifting_reason = reason;
+
+    msg!(
+        "Caveat {} on title deed {} lifted by {}",
+        caveat.sequence_number,
+        title_deed.title_number,
+        lifted_by
+    );
+    Ok(())
+}
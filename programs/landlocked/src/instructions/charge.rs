@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    contexts::{DischargeCharge, RegisterCharge},
+    error::ProtocolError,
+    state::{Charge, TitleDeed},
+    in_registrar_jurisdiction, is_registrar, move_lamports, require_co_owner_signatures,
+};
+
+pub fn register_charge_handler(ctx: Context<RegisterCharge>, amount: u64) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+    require_co_owner_signatures(
+        &ctx.accounts.title_deed,
+        &ctx.accounts.owner.key(),
+        ctx.remaining_accounts,
+    )?;
+
+    require!(amount > 0, ProtocolError::InvalidChargeAmount);
+
+    // charges rank in the order they are registered, matching the charge seeds
+    let title_deed = &mut ctx.accounts.title_deed;
+    title_deed.total_charges = title_deed.total_charges
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+    title_deed.charged_amount = title_deed.charged_amount
+        .checked_add(amount)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+
+    let charge = &mut ctx.accounts.charge;
+    charge.title_deed = title_deed.key();
+    charge.lender = ctx.accounts.lender.key();
+    charge.amount = amount;
+    charge.priority = title_deed.total_charges;
+    charge.registered_by = ctx.accounts.authority.key();
+    charge.registered_at = Clock::get()?.unix_timestamp;
+    charge.discharged_at = None;
+    charge.bump = ctx.bumps.charge;
+
+    msg!(
+        "Charge of {} lamports registered against title deed {} for lender {} (priority {})",
+        amount,
+        title_deed.title_number,
+        charge.lender,
+        charge.priority
+    );
+    Ok(())
+}
+
+pub fn discharge_charge_handler(ctx: Context<DischargeCharge>) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+
+    // the charge is kept as a record of the lender's interest
+    let charge = &mut ctx.accounts.charge;
+    charge.discharged_at = Some(Clock::get()?.unix_timestamp);
+
+    let title_deed = &mut ctx.accounts.title_deed;
+    title_deed.charged_amount = title_deed.charged_amount
+        .checked_sub(charge.amount)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+
+    msg!(
+        "Charge {} on title deed {} discharged by lender {}",
+        charge.priority,
+        title_deed.title_number,
+        charge.lender
+    );
+    Ok(())
+}
+
+// pays each outstanding charge in full from the sale proceeds and discharges it, returning the total paid.
+// charges and their lenders' wallets are passed in pairs, in order of priority
+pub fn pay_off_charges(
+    title_deed: &mut Account<TitleDeed>,
+    proceeds: &AccountInfo,
+    remaining_accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<u64> {
+    let title_deed_key = title_deed.key();
+    let mut accounts = remaining_accounts.iter();
+    let mut last_priority = 0;
+    let mut paid = 0u64;
+
+    while paid < title_deed.charged_amount {
+        let charge_account = accounts.next().ok_or(ProtocolError::MissingChargeAccount)?;
+        let lender_account = accounts.next().ok_or(ProtocolError::MissingChargeAccount)?;
+        require!(charge_account.owner == program_id, ProtocolError::MissingChargeAccount);
+
+        let mut charge = Charge::try_deserialize(&mut &charge_account.try_borrow_data()?[..])?;
+        require!(
+            charge.title_deed == title_deed_key
+                && charge.discharged_at.is_none()
+                // increasing priority also rules out paying the same charge twice
+                && charge.priority > last_priority
+                && lender_account.key() == charge.lender
+                && lender_account.is_writable,
+            ProtocolError::MissingChargeAccount
+        );
+        last_priority = charge.priority;
+
+        move_lamports(proceeds, lender_account, charge.amount)?;
+        paid = paid
+            .checked_add(charge.amount)
+            .ok_or(ProtocolError::ArithmeticOverflow)?;
+
+        charge.discharged_at = Some(now);
+        charge.try_serialize(&mut &mut charge_account.try_borrow_mut_data()?[..])?;
+        msg!(
+            "Charge {} on title deed {} paid off: {} lamports to lender {}",
+            charge.priority,
+            title_deed.title_number,
+            charge.amount,
+            charge.lender
+        );
+    }
+
+    title_deed.charged_amount = 0;
+    Ok(paid)
+}
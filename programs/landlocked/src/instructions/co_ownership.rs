@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    contexts::RegisterCoOwnership,
+    error::ProtocolError,
+    state::{CoOwner, Tenure},
+    in_registrar_jurisdiction, is_registrar, require_co_owner_signatures, MAX_CO_OWNERS,
+    TOTAL_SHARE_BPS,
+};
+
+pub fn register_co_ownership_handler(
+    ctx: Context<RegisterCoOwnership>,
+    tenure: Tenure,
+    co_owners: Vec<CoOwner>,
+) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+
+    // existing co-owners must all consent to the new ownership table
+    require_co_owner_signatures(
+        &ctx.accounts.title_deed,
+        &ctx.accounts.owner.key(),
+        ctx.remaining_accounts,
+    )?;
+    validate_co_owners(tenure, &co_owners, &ctx.accounts.owner.key())?;
+
+    let title_deed = &mut ctx.accounts.title_deed;
+    title_deed.tenure = tenure;
+    title_deed.co_owners = co_owners;
+
+    msg!(
+        "Title deed {} registered with {} co-owners",
+        title_deed.title_number,
+        title_deed.co_owners.len()
+    );
+    Ok(())
+}
+
+// the registered owner must hold a share, and shares must cover the whole title
+fn validate_co_owners(tenure: Tenure, co_owners: &[CoOwner], owner: &Pubkey) -> Result<()> {
+    let count = co_owners.len();
+    let expected_count_ok = match tenure {
+        Tenure::Sole => count == 1,
+        Tenure::JointTenancy | Tenure::TenancyInCommon => (2..=MAX_CO_OWNERS).contains(&count),
+    };
+    require!(expected_count_ok, ProtocolError::InvalidCoOwners);
+    require!(
+        co_owners.iter().any(|co_owner| co_owner.authority == *owner),
+        ProtocolError::InvalidCoOwners
+    );
+    for (i, co_owner) in co_owners.iter().enumerate() {
+        require!(
+            co_owners[..i].iter().all(|other| other.authority != co_owner.authority),
+            ProtocolError::InvalidCoOwners
+        );
+    }
+
+    let total: u32 = co_owners.iter().map(|co_owner| co_owner.share_bps as u32).sum();
+    require!(
+        total == TOTAL_SHARE_BPS as u32 && co_owners.iter().all(|co_owner| co_owner.share_bps > 0),
+        ProtocolError::InvalidCoOwnerShares
+    );
+    // joint tenants hold equal shares - any rounding remainder sits with the first listed
+    if tenure == Tenure::JointTenancy {
+        let equal_share = TOTAL_SHARE_BPS / count as u16;
+        require!(
+            co_owners[0].share_bps == TOTAL_SHARE_BPS - equal_share * (count as u16 - 1)
+                && co_owners[1..].iter().all(|co_owner| co_owner.share_bps == equal_share),
+            ProtocolError::InvalidCoOwnerShares
+        );
+    }
+    Ok(())
+}
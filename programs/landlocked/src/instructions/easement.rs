@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    contexts::RegisterEasement,
+    error::ProtocolError,
+    state::EasementKind,
+    in_registrar_jurisdiction, is_registrar, require_co_owner_signatures, MAX_EASEMENTS,
+    MAX_EASEMENT_DESCRIPTION_LENGTH,
+};
+
+pub fn register_easement_handler(
+    ctx: Context<RegisterEasement>,
+    kind: EasementKind,
+    description: String,
+) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over both titles
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.dominant_title.district_land_registry)?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.servient_title.district_land_registry)?;
+
+    // every owner of both titles consents
+    require_co_owner_signatures(
+        &ctx.accounts.dominant_title,
+        &ctx.accounts.dominant_owner.key(),
+        ctx.remaining_accounts,
+    )?;
+    require_co_owner_signatures(
+        &ctx.accounts.servient_title,
+        &ctx.accounts.servient_owner.key(),
+        ctx.remaining_accounts,
+    )?;
+
+    require!(
+        !description.is_empty() && description.len() <= MAX_EASEMENT_DESCRIPTION_LENGTH,
+        ProtocolError::InvalidEasement
+    );
+    require!(
+        ctx.accounts.dominant_title.easements.len() < MAX_EASEMENTS
+            && ctx.accounts.servient_title.easements.len() < MAX_EASEMENTS,
+        ProtocolError::TooManyEasements
+    );
+
+    // easements are numbered per servient title, matching the easement seeds
+    let easement_key = ctx.accounts.easement.key();
+    let servient_title = &mut ctx.accounts.servient_title;
+    servient_title.total_easements = servient_title.total_easements
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+    servient_title.easements.push(easement_key);
+    ctx.accounts.dominant_title.easements.push(easement_key);
+
+    let easement = &mut ctx.accounts.easement;
+    easement.dominant_title = ctx.accounts.dominant_title.key();
+    easement.servient_title = ctx.accounts.servient_title.key();
+    easement.kind = kind;
+    easement.description = description;
+    easement.registered_by = ctx.accounts.authority.key();
+    easement.registered_at = Clock::get()?.unix_timestamp;
+    easement.sequence_number = ctx.accounts.servient_title.total_easements;
+    easement.bump = ctx.bumps.easement;
+
+    msg!(
+        "Easement {} registered over title deed {} for the benefit of title deed {}",
+        easement_key,
+        ctx.accounts.servient_title.title_number,
+        ctx.accounts.dominant_title.title_number
+    );
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    contexts::{AcceptGiftTransfer, ApproveGiftTransfer, CancelGiftTransfer, InitiateGiftTransfer},
+    error::ProtocolError,
+    state::{OwnershipHistory, TransferType},
+    co_owner_share, in_registrar_jurisdiction, is_registrar, require_co_owner_signatures,
+    transfer_share, TOTAL_SHARE_BPS,
+};
+
+pub fn initiate_gift_transfer_handler(
+    ctx: Context<InitiateGiftTransfer>,
+    share_bps: u16,
+) -> Result<()> {
+    // the whole title is given away by its registered owner with every co-owner's agreement,
+    // a share by the co-owner holding it
+    if share_bps == TOTAL_SHARE_BPS {
+        require!(
+            ctx.accounts.title_deed.owner.authority == ctx.accounts.authority.key(),
+            ProtocolError::Unauthorized
+        );
+        require_co_owner_signatures(
+            &ctx.accounts.title_deed,
+            &ctx.accounts.authority.key(),
+            ctx.remaining_accounts,
+        )?;
+    } else {
+        require!(
+            share_bps > 0
+                && share_bps <= co_owner_share(&ctx.accounts.title_deed, &ctx.accounts.authority.key()),
+            ProtocolError::InvalidShare
+        );
+    }
+
+    let gift_transfer = &mut ctx.accounts.gift_transfer;
+    gift_transfer.title_deed = ctx.accounts.title_deed.key();
+    gift_transfer.donor = ctx.accounts.authority.key();
+    gift_transfer.recipient = (*ctx.accounts.recipient).clone();
+    gift_transfer.share_bps = share_bps;
+    gift_transfer.initiated_at = Clock::get()?.unix_timestamp;
+    gift_transfer.accepted_at = None;
+    gift_transfer.bump = ctx.bumps.gift_transfer;
+
+    msg!(
+        "Title deed {} offered as a gift by {} to {}",
+        ctx.accounts.title_deed.title_number,
+        gift_transfer.donor,
+        gift_transfer.recipient.authority
+    );
+    Ok(())
+}
+
+pub fn accept_gift_transfer_handler(ctx: Context<AcceptGiftTransfer>) -> Result<()> {
+    let gift_transfer = &mut ctx.accounts.gift_transfer;
+    gift_transfer.accepted_at = Some(Clock::get()?.unix_timestamp);
+
+    msg!(
+        "Gift of title deed {} accepted by {}",
+        ctx.accounts.title_deed.title_number,
+        gift_transfer.recipient.authority
+    );
+    Ok(())
+}
+
+pub fn approve_gift_transfer_handler(ctx: Context<ApproveGiftTransfer>) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+
+    let title_deed_key = ctx.accounts.title_deed.key();
+    let recipient = ctx.accounts.gift_transfer.recipient.clone();
+    let share_bps = ctx.accounts.gift_transfer.share_bps;
+    let title_deed = &mut ctx.accounts.title_deed;
+    let previous_owner = ctx.accounts.gift_transfer.donor;
+
+    // sequence number of the new history entry, matching the ownership history seeds
+    title_deed.total_transfers = title_deed.total_transfers
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+    let sequence_number = title_deed.total_transfers;
+
+    let current_owner = recipient.authority;
+    // checks the donor still holds the share
+    transfer_share(title_deed, &previous_owner, recipient, share_bps)?;
+
+    ctx.accounts.ownership_history.set_inner(OwnershipHistory {
+        title_deed: title_deed_key,
+        previous_owner,
+        current_owner,
+        transferred_at: Clock::get()?.unix_timestamp,
+        transfer_type: TransferType::Gift,
+        share_bps,
+        co_owners: title_deed.co_owners.clone(),
+        parent_titles: Vec::new(),
+        sequence_number,
+        bump: ctx.bumps.ownership_history,
+    });
+
+    // gift transfer account is closed on exit
+    msg!(
+        "Title deed {} gifted from {} to {}. Ownership history recorded (sequence: {})",
+        title_deed.title_number,
+        previous_owner,
+        current_owner,
+        sequence_number
+    );
+    Ok(())
+}
+
+pub fn cancel_gift_transfer_handler(ctx: Context<CancelGiftTransfer>) -> Result<()> {
+    // gift transfer account is closed on exit, refunding its rent to the donor
+    msg!(
+        "Gift of title deed {} cancelled by {}",
+        ctx.accounts.title_deed.title_number,
+        ctx.accounts.authority.key()
+    );
+    Ok(())
+}
@@ -1 +1,233 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+
+use crate::{
+    error::ProtocolError,
+    state::{CoOwner, Registrar, Tenure, TitleDeed, User},
+    MAX_CO_OWNERS, TOTAL_SHARE_BPS,
+};
+
+pub fn is_registrar(authority: &Pubkey, registrar_account: &AccountInfo) -> Result<Registrar> {
+    // Check account exists
+    require!(
+        registrar_account.lamports() > 0,
+        ProtocolError::InvalidRegistrar
+    );
+    
+    // Deserialize registrar
+    let registrar = Registrar::try_deserialize(&mut &registrar_account.data.borrow()[..])?;
+    
+    // Verify it's in good standing, active and matches authority
+    require!(
+        registrar.revoked_at.is_none(),
+        ProtocolError::RegistrarRevoked
+    );
+    require!(
+        registrar.suspended_at.is_none(),
+        ProtocolError::RegistrarSuspended
+    );
+    require!(
+        registrar.is_active,
+        ProtocolError::InvalidRegistrar
+    );
+    require!(
+        registrar.authority == *authority,
+        ProtocolError::InvalidRegistrar
+    );
+    
+    Ok(registrar)
+}
+
+// ensures the district land registry falls under the registrar's jurisdiction
+pub fn in_registrar_jurisdiction(registrar: &Registrar, district_land_registry: &str) -> Result<()> {
+    require!(
+        registrar.districts.iter().any(|district| district == district_land_registry),
+        ProtocolError::OutsideRegistrarJurisdiction
+    );
+    Ok(())
+}
+
+// makes the user the sole owner and authority of the title deed
+pub fn make_sole_owner(title_deed: &mut TitleDeed, owner: User) {
+    title_deed.authority = owner.authority;
+    title_deed.tenure = Tenure::Sole;
+    title_deed.co_owners = vec![CoOwner {
+        authority: owner.authority,
+        share_bps: TOTAL_SHARE_BPS,
+    }];
+    title_deed.owner = owner;
+}
+
+// dealings on a co-owned title need every other co-owner to co-sign through the remaining accounts
+pub fn require_co_owner_signatures(
+    title_deed: &TitleDeed,
+    signer: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    for co_owner in title_deed.co_owners.iter().filter(|co_owner| co_owner.authority != *signer) {
+        require!(
+            remaining_accounts
+                .iter()
+                .any(|account| account.key() == co_owner.authority && account.is_signer),
+            ProtocolError::MissingCoOwnerSignature
+        );
+    }
+    Ok(())
+}
+
+// basis points of the title held by the authority, zero if they are not a co-owner
+pub fn co_owner_share(title_deed: &TitleDeed, authority: &Pubkey) -> u16 {
+    title_deed
+        .co_owners
+        .iter()
+        .find(|co_owner| co_owner.authority == *authority)
+        .map_or(0, |co_owner| co_owner.share_bps)
+}
+
+// moves a share of the title between owners - transferring a partial share severs a joint tenancy
+pub fn transfer_share(
+    title_deed: &mut TitleDeed,
+    from: &Pubkey,
+    to: User,
+    share_bps: u16,
+) -> Result<()> {
+    // the whole title passes from its registered owner, with every co-owner's consent given up front
+    if share_bps == TOTAL_SHARE_BPS {
+        require!(
+            title_deed.owner.authority == *from,
+            ProtocolError::TitleAuthorityMismatch
+        );
+        make_sole_owner(title_deed, to);
+        return Ok(());
+    }
+    require!(
+        share_bps > 0 && share_bps <= co_owner_share(title_deed, from),
+        ProtocolError::InvalidShare
+    );
+
+    for co_owner in title_deed.co_owners.iter_mut() {
+        if co_owner.authority == *from {
+            co_owner.share_bps -= share_bps;
+        }
+    }
+    match title_deed.co_owners.iter_mut().find(|co_owner| co_owner.authority == to.authority) {
+        Some(co_owner) => co_owner.share_bps += share_bps,
+        None => {
+            require!(
+                title_deed.co_owners.len() < MAX_CO_OWNERS,
+                ProtocolError::InvalidCoOwners
+            );
+            title_deed.co_owners.push(CoOwner {
+                authority: to.authority,
+                share_bps,
+            });
+        }
+    }
+    title_deed.co_owners.retain(|co_owner| co_owner.share_bps > 0);
+
+    // the recipient becomes the registered owner if the registered owner sold out
+    if co_owner_share(title_deed, &title_deed.owner.authority) == 0 {
+        title_deed.owner = to;
+    }
+    title_deed.authority = title_deed.owner.authority;
+    title_deed.tenure = if title_deed.co_owners.len() == 1 {
+        Tenure::Sole
+    } else {
+        Tenure::TenancyInCommon
+    };
+    Ok(())
+}
+
+// creates a program account at a PDA the program signs for, paid for by the payer
+// an address that was pre-funded is topped up to rent exemption, allocated and assigned instead
+pub fn create_program_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let lamports_required = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+    let signer = &[signer_seeds];
+    if current_lamports == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer,
+            ),
+            lamports_required,
+            space as u64,
+            program_id,
+        )?;
+        return Ok(());
+    }
+
+    let top_up = lamports_required.saturating_sub(current_lamports);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            signer,
+        ),
+        program_id,
+    )?;
+    Ok(())
+}
+
+// closes an account owned by the program if it exists, returning its rent to the destination
+pub fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    if account.owner != program_id {
+        return Ok(());
+    }
+    move_lamports(account, destination, account.lamports())?;
+    account.assign(&System::id());
+    account.resize(0)?;
+    Ok(())
+}
+
+// moves lamports out of an account owned by the program
+pub fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let from_lamports = from.lamports();
+    let to_lamports = to.lamports();
+    **from.try_borrow_mut_lamports()? = from_lamports
+        .checked_sub(amount)
+        .ok_or(ProtocolError::ArithmeticUnderflow)?;
+    **to.try_borrow_mut_lamports()? = to_lamports
+        .checked_add(amount)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+    Ok(())
+}
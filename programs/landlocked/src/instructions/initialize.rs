@@ -1,4 +1,8 @@
-use crate::{contexts::InitializeLandRegistry, error::ProtocolError};
+use crate::{
+    contexts::InitializeLandRegistry, default_approval_threshold, error::ProtocolError,
+    state::ProtocolConfig, DEFAULT_AGREEMENT_VALIDITY_DAYS, DEFAULT_ESCROW_PERIOD_DAYS,
+    DEFAULT_SEARCH_VALIDITY_DAYS, MAX_ADMINS,
+};
 use anchor_lang::prelude::*;
 
 pub fn handler(ctx: Context<InitializeLandRegistry>, admins: Vec<Pubkey>) -> Result<()> {
@@ -9,13 +13,24 @@ pub fn handler(ctx: Context<InitializeLandRegistry>, admins: Vec<Pubkey>) -> Res
     );
 
     // Validate admin list constraints
-    require!(admins.len() <= 5, ProtocolError::InvalidAdmin);
+    require!(admins.len() <= MAX_ADMINS, ProtocolError::InvalidAdmin);
     require!(!admins.is_empty(), ProtocolError::InvalidAdmin);
+    // a duplicated admin would count towards the approval threshold but could only approve once
+    for (i, admin) in admins.iter().enumerate() {
+        require!(!admins[..i].contains(admin), ProtocolError::DuplicateAdmin);
+    }
 
     // Initialize the protocol state with the admin list
     let protocol_state = &mut ctx.accounts.protocol_state;
     protocol_state.admins = admins;
     protocol_state.is_paused = false;
+    protocol_state.proposal_count = 0;
+    protocol_state.config = ProtocolConfig {
+        approval_threshold: default_approval_threshold(protocol_state.admins.len()),
+        search_validity_days: DEFAULT_SEARCH_VALIDITY_DAYS,
+        escrow_period_days: DEFAULT_ESCROW_PERIOD_DAYS,
+        agreement_validity_days: DEFAULT_AGREEMENT_VALIDITY_DAYS,
+    };
     protocol_state.bump = ctx.bumps.protocol_state;
 
     msg!("Protocol initialized with {} admins", protocol_state.admins.len());
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    contexts::RegisterLease,
+    error::ProtocolError,
+    state::RegisteredLease,
+    in_registrar_jurisdiction, is_registrar, require_co_owner_signatures, MAX_LEASES,
+    MAX_PERMITTED_USE_LENGTH,
+};
+
+pub fn register_lease_handler(
+    ctx: Context<RegisterLease>,
+    lessee: Pubkey,
+    term_start: i64,
+    term_end: i64,
+    rent: u64,
+    permitted_use: String,
+) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+    require_co_owner_signatures(
+        &ctx.accounts.title_deed,
+        &ctx.accounts.owner.key(),
+        ctx.remaining_accounts,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        term_end > term_start
+            && term_end > now
+            && !permitted_use.is_empty()
+            && permitted_use.len() <= MAX_PERMITTED_USE_LENGTH,
+        ProtocolError::InvalidLeaseTerms
+    );
+
+    // leases that have run out make room for new ones
+    let title_deed = &mut ctx.accounts.title_deed;
+    title_deed.leases.retain(|lease| lease.term_end > now);
+    require!(title_deed.leases.len() < MAX_LEASES, ProtocolError::TooManyLeases);
+
+    // leases are numbered in the order they are registered, matching the lease seeds
+    title_deed.total_leases = title_deed.total_leases
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+    title_deed.leases.push(RegisteredLease {
+        lease: ctx.accounts.lease.key(),
+        term_end,
+    });
+
+    let lease = &mut ctx.accounts.lease;
+    lease.title_deed = title_deed.key();
+    lease.lessor = title_deed.owner.authority;
+    lease.lessee = lessee;
+    lease.term_start = term_start;
+    lease.term_end = term_end;
+    lease.rent = rent;
+    lease.permitted_use = permitted_use;
+    lease.registered_by = ctx.accounts.authority.key();
+    lease.registered_at = now;
+    lease.sequence_number = title_deed.total_leases;
+    lease.bump = ctx.bumps.lease;
+
+    msg!(
+        "Lease {} of title deed {} registered to lessee {} until {}",
+        lease.sequence_number,
+        title_deed.title_number,
+        lessee,
+        term_end
+    );
+    Ok(())
+}
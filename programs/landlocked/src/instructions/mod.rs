@@ -1,13 +1,39 @@
 pub mod initialize;
 pub mod confirm_admin_account;
+pub mod admin;
+pub mod proposal;
+pub mod protocol;
 pub mod registrar;
 pub mod user;
 pub mod title_deed;
+pub mod gift;
+pub mod succession;
+pub mod co_ownership;
+pub mod subdivision;
+pub mod amalgamation;
+pub mod charge;
+pub mod caveat;
+pub mod lease;
+pub mod easement;
 pub mod helpers;
 
+// `initialize` and `confirm_admin_account` both expose a `handler`, which lib.rs calls by module path
+#[allow(ambiguous_glob_reexports)]
 pub use initialize::*;
 pub use confirm_admin_account::*;
+pub use admin::*;
+pub use proposal::*;
+pub use protocol::*;
 pub use registrar::*;
 pub use user::*;
 pub use title_deed::*;
+pub use gift::*;
+pub use succession::*;
+pub use co_ownership::*;
+pub use subdivision::*;
+pub use amalgamation::*;
+pub use charge::*;
+pub use caveat::*;
+pub use lease::*;
+pub use easement::*;
 pub use helpers::*;
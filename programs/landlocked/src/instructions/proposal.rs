@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ProtocolError,
+    is_admin,
+    state::{Proposal, ProposalAction, ProtocolState},
+    ApproveProposal, CreateProposal, PROPOSAL_VALIDITY_DAYS, SECONDS_PER_DAY,
+};
+
+// Admin drafts a privileged action, the proposer's approval is recorded automatically
+pub fn create_proposal_handler(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+    // validate that the authority is an admin
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    let clock = Clock::get()?;
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = protocol_state.proposal_count;
+    proposal.proposer = ctx.accounts.authority.key();
+    proposal.action = action;
+    proposal.approvals = vec![ctx.accounts.authority.key()];
+    proposal.created_at = clock.unix_timestamp;
+    // stale proposals cannot be approved or executed long after they were drafted
+    proposal.expires_at = clock
+        .unix_timestamp
+        .checked_add(PROPOSAL_VALIDITY_DAYS * SECONDS_PER_DAY)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+    proposal.executed_at = None;
+    proposal.bump = ctx.bumps.proposal;
+
+    protocol_state.proposal_count = protocol_state
+        .proposal_count
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+
+    msg!(
+        "Proposal {} created by admin {}",
+        proposal.id,
+        ctx.accounts.authority.key()
+    );
+    Ok(())
+}
+
+pub fn approve_proposal_handler(ctx: Context<ApproveProposal>) -> Result<()> {
+    // validate that the authority is an admin
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        Clock::get()?.unix_timestamp <= proposal.expires_at,
+        ProtocolError::ProposalExpired
+    );
+    require!(
+        !proposal.approvals.contains(&ctx.accounts.authority.key()),
+        ProtocolError::ProposalAlreadyApproved
+    );
+    // drop approvals from admins that have since been removed or rotated out so the list
+    // never outgrows the admin set it was sized for
+    let admins = &ctx.accounts.protocol_state.admins;
+    proposal.approvals.retain(|approver| admins.contains(approver));
+    proposal.approvals.push(ctx.accounts.authority.key());
+
+    msg!(
+        "Proposal {} approved by admin {} ({} approvals)",
+        proposal.id,
+        ctx.accounts.authority.key(),
+        proposal.approvals.len()
+    );
+    Ok(())
+}
+
+// approval threshold the protocol starts with - a simple majority of the admins
+pub fn default_approval_threshold(admin_count: usize) -> u8 {
+    (admin_count / 2 + 1) as u8
+}
+
+// ensures the proposal reached the approval threshold and marks it as executed
+// approvals from admins that have since been removed are not counted
+pub fn execute_proposal(proposal: &mut Proposal, protocol_state: &ProtocolState) -> Result<()> {
+    require!(
+        proposal.executed_at.is_none(),
+        ProtocolError::ProposalAlreadyExecuted
+    );
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= proposal.expires_at, ProtocolError::ProposalExpired);
+
+    let approvals = proposal
+        .approvals
+        .iter()
+        .filter(|approver| protocol_state.admins.contains(approver))
+        .count();
+    require!(
+        approvals >= protocol_state.config.approval_threshold as usize,
+        ProtocolError::ProposalThresholdNotReached
+    );
+
+    proposal.executed_at = Some(now);
+    Ok(())
+}
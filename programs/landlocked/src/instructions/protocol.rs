@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ProtocolError, execute_proposal, is_admin, state::ProposalAction, PauseProtocol,
+    UnpauseProtocol, UpdateProtocolConfig,
+};
+
+// Admins freeze all dealings on the registry e.g. during a security incident
+pub fn pause_protocol_handler(ctx: Context<PauseProtocol>) -> Result<()> {
+    // validate that the authority is an admin
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    require!(
+        ctx.accounts.proposal.action == ProposalAction::PauseProtocol,
+        ProtocolError::ProposalActionMismatch
+    );
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.is_paused = true;
+
+    msg!("Protocol paused by admin {}", ctx.accounts.authority.key());
+    Ok(())
+}
+
+// Admins resume dealings on the registry
+pub fn unpause_protocol_handler(ctx: Context<UnpauseProtocol>) -> Result<()> {
+    // validate that the authority is an admin
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    require!(
+        ctx.accounts.proposal.action == ProposalAction::UnpauseProtocol,
+        ProtocolError::ProposalActionMismatch
+    );
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    protocol_state.is_paused = false;
+
+    msg!("Protocol unpaused by admin {}", ctx.accounts.authority.key());
+    Ok(())
+}
+
+// Admins change the protocol configuration e.g. the number of approvals a proposal needs
+pub fn update_protocol_config_handler(ctx: Context<UpdateProtocolConfig>) -> Result<()> {
+    // validate that the authority is an admin
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    let ProposalAction::UpdateConfig { config } = ctx.accounts.proposal.action.clone() else {
+        return err!(ProtocolError::ProposalActionMismatch);
+    };
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
+    let protocol_state = &mut ctx.accounts.protocol_state;
+    require!(
+        config.approval_threshold >= 1
+            && config.approval_threshold as usize <= protocol_state.admins.len(),
+        ProtocolError::InvalidApprovalThreshold
+    );
+    require!(
+        config.search_validity_days >= 1,
+        ProtocolError::InvalidSearchValidityPeriod
+    );
+    require!(
+        config.escrow_period_days >= 1,
+        ProtocolError::InvalidEscrowPeriod
+    );
+    require!(
+        config.agreement_validity_days >= 1,
+        ProtocolError::InvalidAgreementValidityPeriod
+    );
+    protocol_state.config = config;
+
+    msg!(
+        "Protocol config updated by admin {}: approval threshold {}, search validity {} days, escrow period {} days, agreement validity {} days",
+        ctx.accounts.authority.key(),
+        protocol_state.config.approval_threshold,
+        protocol_state.config.search_validity_days,
+        protocol_state.config.escrow_period_days,
+        protocol_state.config.agreement_validity_days
+    );
+    Ok(())
+}
@@ -1,8 +1,25 @@
 use anchor_lang::prelude::*;
 
-use crate::{error::ProtocolError, is_admin, AddRegistrar, ConfirmRegistrarAccount};
+use crate::{
+    error::ProtocolError, execute_proposal, is_admin, state::ProposalAction, AddRegistrar,
+    ConfirmRegistrarAccount, ReinstateRegistrar, RevokeRegistrar, SuspendRegistrar,
+    UpdateRegistrarDistricts, MAX_DISTRICT_LENGTH, MAX_REGISTRAR_DISTRICTS,
+};
 
-// Admin adds a registrar address (creates account, not active yet)
+// registrar must be assigned at least one district land registry, each a valid name
+fn validate_registrar_districts(districts: &[String]) -> Result<()> {
+    require!(
+        !districts.is_empty()
+            && districts.len() <= MAX_REGISTRAR_DISTRICTS
+            && districts
+                .iter()
+                .all(|district| !district.is_empty() && district.len() <= MAX_DISTRICT_LENGTH),
+        ProtocolError::InvalidRegistrarDistricts
+    );
+    Ok(())
+}
+
+// Admin adds an approved registrar address (creates account, not active yet)
 pub fn add_registrar_handler(
     ctx: Context<AddRegistrar>,
     address: Pubkey,
@@ -13,11 +30,24 @@ pub fn add_registrar_handler(
     // validate that the authority is an admin
     is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
 
+    // the registrar address and districts must have been approved by the admins
+    let ProposalAction::AddRegistrar { registrar, districts } = ctx.accounts.proposal.action.clone()
+    else {
+        return err!(ProtocolError::ProposalActionMismatch);
+    };
+    require!(registrar == address, ProtocolError::ProposalActionMismatch);
+    validate_registrar_districts(&districts)?;
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
     // initialize the registrar account (not active, waiting for confirmation)
     let registrar = &mut ctx.accounts.registrar;
     registrar.authority = address;
     registrar.added_by = ctx.accounts.authority.key();
+    registrar.districts = districts;
     registrar.is_active = false;
+    registrar.suspended_at = None;
+    registrar.suspension_reason = None;
+    registrar.revoked_at = None;
     registrar.id_number = id_number.clone();
     registrar.first_name = first_name.clone();
     registrar.last_name = last_name.clone();
@@ -44,3 +74,93 @@ pub fn confirm_registrar_handler(ctx: Context<ConfirmRegistrarAccount>) -> Resul
     );
     Ok(())
 }
+
+// Admins suspend a registrar e.g. one under investigation for corruption
+pub fn suspend_registrar_handler(ctx: Context<SuspendRegistrar>) -> Result<()> {
+    // validate that the authority is an admin
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    let ProposalAction::SuspendRegistrar { registrar, reason } = ctx.accounts.proposal.action else {
+        return err!(ProtocolError::ProposalActionMismatch);
+    };
+    require!(
+        registrar == ctx.accounts.registrar.authority,
+        ProtocolError::ProposalActionMismatch
+    );
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
+    let registrar = &mut ctx.accounts.registrar;
+    registrar.suspended_at = Some(Clock::get()?.unix_timestamp);
+    registrar.suspension_reason = Some(reason);
+
+    msg!("Registrar {} suspended", registrar.authority);
+    Ok(())
+}
+
+// Admins lift a registrar's suspension
+pub fn reinstate_registrar_handler(ctx: Context<ReinstateRegistrar>) -> Result<()> {
+    // validate that the authority is an admin
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    require!(
+        ctx.accounts.proposal.action
+            == ProposalAction::ReinstateRegistrar {
+                registrar: ctx.accounts.registrar.authority
+            },
+        ProtocolError::ProposalActionMismatch
+    );
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
+    let registrar = &mut ctx.accounts.registrar;
+    registrar.suspended_at = None;
+    registrar.suspension_reason = None;
+
+    msg!("Registrar {} reinstated", registrar.authority);
+    Ok(())
+}
+
+// Admins permanently revoke a registrar
+// the account is kept so that the registrar address can never be added again
+pub fn revoke_registrar_handler(ctx: Context<RevokeRegistrar>) -> Result<()> {
+    // validate that the authority is an admin
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    require!(
+        ctx.accounts.proposal.action
+            == ProposalAction::RevokeRegistrar {
+                registrar: ctx.accounts.registrar.authority
+            },
+        ProtocolError::ProposalActionMismatch
+    );
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
+    let registrar = &mut ctx.accounts.registrar;
+    registrar.revoked_at = Some(Clock::get()?.unix_timestamp);
+
+    msg!("Registrar {} revoked", registrar.authority);
+    Ok(())
+}
+
+// Admins change the district land registries under a registrar's jurisdiction
+pub fn update_registrar_districts_handler(ctx: Context<UpdateRegistrarDistricts>) -> Result<()> {
+    // validate that the authority is an admin
+    is_admin(ctx.accounts.authority.key(), &ctx.accounts.protocol_state)?;
+
+    let ProposalAction::UpdateRegistrarDistricts { registrar, districts } =
+        ctx.accounts.proposal.action.clone()
+    else {
+        return err!(ProtocolError::ProposalActionMismatch);
+    };
+    require!(
+        registrar == ctx.accounts.registrar.authority,
+        ProtocolError::ProposalActionMismatch
+    );
+    validate_registrar_districts(&districts)?;
+    execute_proposal(&mut ctx.accounts.proposal, &ctx.accounts.protocol_state)?;
+
+    let registrar = &mut ctx.accounts.registrar;
+    registrar.districts = districts;
+
+    msg!("Registrar {} districts updated", registrar.authority);
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    contexts::SubdivideTitleDeed,
+    error::ProtocolError,
+    state::{OwnershipHistory, Parcel, TitleDeed, TitleNumberLookup, TransferType},
+    create_program_account, in_registrar_jurisdiction, is_registrar, require_co_owner_signatures,
+    ACREAGE_TOLERANCE, MAX_SUBDIVISION_PARCELS, MAX_TITLE_NUMBER_LENGTH, TOTAL_SHARE_BPS,
+};
+
+// title deed, title number lookup and ownership history of each parcel
+const ACCOUNTS_PER_PARCEL: usize = 3;
+
+// remaining accounts are created as parcels, so they share the lifetime of the instruction's accounts
+pub fn subdivide_title_deed_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SubdivideTitleDeed<'info>>,
+    parcels: Vec<Parcel>,
+) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+
+    // a lease binds the whole title, so it cannot be split up while one is in force
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.title_deed.leases.iter().all(|lease| lease.term_end <= now),
+        ProtocolError::TitleIsLeased
+    );
+
+    require!(
+        parcels.len() >= 2 && parcels.len() <= MAX_SUBDIVISION_PARCELS,
+        ProtocolError::InvalidParcels
+    );
+    require!(
+        ctx.remaining_accounts.len() >= parcels.len() * ACCOUNTS_PER_PARCEL,
+        ProtocolError::InvalidParcelAccount
+    );
+    let (parcel_accounts, co_owner_accounts) =
+        ctx.remaining_accounts.split_at(parcels.len() * ACCOUNTS_PER_PARCEL);
+    // every co-owner has to consent to their land being split up
+    require_co_owner_signatures(
+        &ctx.accounts.title_deed,
+        &ctx.accounts.owner.key(),
+        co_owner_accounts,
+    )?;
+    require!(
+        parcels.iter().all(|parcel| {
+            !parcel.title_number.is_empty() && parcel.title_number.len() <= MAX_TITLE_NUMBER_LENGTH
+        }),
+        ProtocolError::InvalidTitleDeedDetails
+    );
+    let total_acreage: f64 = parcels.iter().map(|parcel| parcel.acreage).sum();
+    require!(
+        parcels.iter().all(|parcel| parcel.acreage > 0.0)
+            && (total_acreage - ctx.accounts.title_deed.acreage).abs() <= ACREAGE_TOLERANCE,
+        ProtocolError::InvalidParcelAcreage
+    );
+
+    let parent_key = ctx.accounts.title_deed.key();
+    let parent = &ctx.accounts.title_deed;
+    let payer = ctx.accounts.authority.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let mut child_titles = Vec::with_capacity(parcels.len());
+
+    for (parcel, accounts) in parcels.iter().zip(parcel_accounts.chunks(ACCOUNTS_PER_PARCEL)) {
+        let (title_deed_info, lookup_info, history_info) = (&accounts[0], &accounts[1], &accounts[2]);
+
+        // parcel accounts must be the PDAs of its title number
+        let (title_deed_key, title_deed_bump) = Pubkey::find_program_address(
+            &[b"title_deed", parcel.title_number.as_bytes()],
+            ctx.program_id,
+        );
+        let (lookup_key, lookup_bump) = Pubkey::find_program_address(
+            &[b"title_number_lookup", parcel.title_number.as_bytes()],
+            ctx.program_id,
+        );
+        let (history_key, history_bump) = Pubkey::find_program_address(
+            &[b"ownership_history", title_deed_key.as_ref(), 0u64.to_le_bytes().as_ref()],
+            ctx.program_id,
+        );
+        require!(
+            title_deed_info.key() == title_deed_key
+                && lookup_info.key() == lookup_key
+                && history_info.key() == history_key,
+            ProtocolError::InvalidParcelAccount
+        );
+
+        // creating the accounts fails if the title number is already registered
+        create_program_account(
+            &payer,
+            title_deed_info,
+            &system_program,
+            ctx.program_id,
+            8 + TitleDeed::INIT_SPACE,
+            &[b"title_deed", parcel.title_number.as_bytes(), &[title_deed_bump]],
+        )?;
+        create_program_account(
+            &payer,
+            lookup_info,
+            &system_program,
+            ctx.program_id,
+            8 + TitleNumberLookup::INIT_SPACE,
+            &[b"title_number_lookup", parcel.title_number.as_bytes(), &[lookup_bump]],
+        )?;
+        create_program_account(
+            &payer,
+            history_info,
+            &system_program,
+            ctx.program_id,
+            8 + OwnershipHistory::INIT_SPACE,
+            &[b"ownership_history", title_deed_key.as_ref(), 0u64.to_le_bytes().as_ref(), &[history_bump]],
+        )?;
+
+        // parcels are held by the parent's owners on the same terms
+        let child = TitleDeed {
+            owner: parent.owner.clone(),
+            authority: parent.owner.authority,
+            title_number: parcel.title_number.clone(),
+            location: parent.location.clone(),
+            acreage: parcel.acreage,
+            district_land_registry: parent.district_land_registry.clone(),
+            registration_date: now,
+            registry_mapsheet_number: parent.registry_mapsheet_number,
+            is_for_sale: false,
+            listed_shares: 0,
+            total_transfers: 0,
+            total_agreements: 0,
+            in_succession: false,
+            tenure: parent.tenure,
+            co_owners: parent.co_owners.clone(),
+            retired_at: None,
+            parent_titles: vec![parent_key],
+            child_titles: Vec::new(),
+            total_charges: 0,
+            charged_amount: 0,
+            total_caveats: 0,
+            active_caveats: 0,
+            total_leases: 0,
+            leases: Vec::new(),
+            total_easements: 0,
+            easements: Vec::new(),
+            bump: title_deed_bump,
+        };
+        child.try_serialize(&mut &mut title_deed_info.try_borrow_mut_data()?[..])?;
+
+        let title_number_lookup = TitleNumberLookup {
+            title_number: parcel.title_number.clone(),
+            title_deed: title_deed_key,
+            bump: lookup_bump,
+        };
+        title_number_lookup.try_serialize(&mut &mut lookup_info.try_borrow_mut_data()?[..])?;
+
+        let ownership_history = OwnershipHistory {
+            title_deed: title_deed_key,
+            previous_owner: parent.owner.authority,
+            current_owner: parent.owner.authority,
+            transferred_at: now,
+            transfer_type: TransferType::Subdivision,
+            share_bps: TOTAL_SHARE_BPS,
+            co_owners: parent.co_owners.clone(),
+            parent_titles: vec![parent_key],
+            sequence_number: 0,
+            bump: history_bump,
+        };
+        ownership_history.try_serialize(&mut &mut history_info.try_borrow_mut_data()?[..])?;
+
+        child_titles.push(title_deed_key);
+    }
+
+    // the retired title keeps its owners for the record but can no longer be dealt with
+    let title_deed = &mut ctx.accounts.title_deed;
+    title_deed.authority = Pubkey::default();
+    title_deed.retired_at = Some(now);
+    title_deed.child_titles = child_titles;
+
+    msg!(
+        "Title deed {} retired and subdivided into {} parcels",
+        title_deed.title_number,
+        parcels.len()
+    );
+    Ok(())
+}
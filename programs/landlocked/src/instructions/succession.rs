@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    contexts::{RecordSuccession, RecordSurvivorship, TransferToBeneficiary},
+    error::ProtocolError,
+    state::{CoOwner, DeathRecord, OwnershipHistory, Tenure, TransferType},
+    co_owner_share, create_program_account, in_registrar_jurisdiction, is_registrar, transfer_share,
+    MAX_BENEFICIARIES, MAX_COURT_GRANT_REFERENCE_LENGTH, TOTAL_SHARE_BPS,
+};
+
+pub fn record_succession_handler(
+    ctx: Context<RecordSuccession>,
+    court_grant_reference: String,
+    administrator: Pubkey,
+    beneficiaries: Vec<Pubkey>,
+) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+
+    require!(
+        !court_grant_reference.is_empty()
+            && court_grant_reference.len() <= MAX_COURT_GRANT_REFERENCE_LENGTH,
+        ProtocolError::InvalidCourtGrantReference
+    );
+    require!(
+        !beneficiaries.is_empty() && beneficiaries.len() <= MAX_BENEFICIARIES,
+        ProtocolError::InvalidBeneficiaries
+    );
+
+    // the deceased can no longer deal with any of their titles, not just this one
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.deceased.deceased_at.get_or_insert(now);
+    record_death(
+        &ctx.accounts.authority,
+        &ctx.accounts.death_record,
+        &ctx.accounts.system_program,
+        ctx.program_id,
+        ctx.accounts.deceased.authority,
+        ctx.bumps.death_record,
+        now,
+    )?;
+
+    // freeze the title until it has passed to the beneficiaries
+    let title_deed = &mut ctx.accounts.title_deed;
+    title_deed.in_succession = true;
+
+    let succession = &mut ctx.accounts.succession;
+    succession.title_deed = title_deed.key();
+    succession.deceased = ctx.accounts.deceased.authority;
+    succession.administrator = administrator;
+    succession.court_grant_reference = court_grant_reference;
+    succession.beneficiaries = beneficiaries;
+    succession.recorded_by = ctx.accounts.authority.key();
+    succession.recorded_at = now;
+    succession.bump = ctx.bumps.succession;
+
+    msg!(
+        "Title deed {} under succession of deceased owner {} (grant {}), administered by {}",
+        title_deed.title_number,
+        succession.deceased,
+        succession.court_grant_reference,
+        administrator
+    );
+    Ok(())
+}
+
+pub fn transfer_to_beneficiary_handler(ctx: Context<TransferToBeneficiary>, share_bps: u16) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+
+    let title_deed_key = ctx.accounts.title_deed.key();
+    let deceased = ctx.accounts.succession.deceased;
+    let title_deed = &mut ctx.accounts.title_deed;
+
+    // the deceased's share is split among the beneficiaries one transfer at a time
+    require!(
+        share_bps > 0 && share_bps <= co_owner_share(title_deed, &deceased),
+        ProtocolError::InvalidShare
+    );
+
+    // sequence number of the new history entry, matching the ownership history seeds
+    title_deed.total_transfers = title_deed.total_transfers
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+    let sequence_number = title_deed.total_transfers;
+
+    transfer_share(title_deed, &deceased, (*ctx.accounts.beneficiary).clone(), share_bps)?;
+    let estate_settled = co_owner_share(title_deed, &deceased) == 0;
+    if estate_settled {
+        title_deed.is_for_sale = false;
+        title_deed.in_succession = false;
+    }
+
+    ctx.accounts.ownership_history.set_inner(OwnershipHistory {
+        title_deed: title_deed_key,
+        previous_owner: deceased,
+        current_owner: ctx.accounts.beneficiary.authority,
+        transferred_at: Clock::get()?.unix_timestamp,
+        transfer_type: TransferType::Inheritance,
+        share_bps,
+        co_owners: title_deed.co_owners.clone(),
+        parent_titles: Vec::new(),
+        sequence_number,
+        bump: ctx.bumps.ownership_history,
+    });
+
+    msg!(
+        "{} basis points of title deed {} inherited by {} from {}. Ownership history recorded (sequence: {})",
+        share_bps,
+        title_deed.title_number,
+        ctx.accounts.beneficiary.authority,
+        deceased,
+        sequence_number
+    );
+
+    // the succession is closed once nothing is left to pass on
+    if estate_settled {
+        ctx.accounts.succession.close(ctx.accounts.authority.to_account_info())?;
+    }
+    Ok(())
+}
+
+pub fn record_survivorship_handler(ctx: Context<RecordSurvivorship>) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+
+    // the deceased can no longer deal with any of their titles, not just this one
+    let now = Clock::get()?.unix_timestamp;
+    let deceased = ctx.accounts.deceased.authority;
+    ctx.accounts.deceased.deceased_at.get_or_insert(now);
+    record_death(
+        &ctx.accounts.authority,
+        &ctx.accounts.death_record,
+        &ctx.accounts.system_program,
+        ctx.program_id,
+        deceased,
+        ctx.bumps.death_record,
+        now,
+    )?;
+
+    let title_deed_key = ctx.accounts.title_deed.key();
+    let title_deed = &mut ctx.accounts.title_deed;
+    let share_bps = co_owner_share(title_deed, &deceased);
+
+    // sequence number of the new history entry, matching the ownership history seeds
+    title_deed.total_transfers = title_deed.total_transfers
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+    let sequence_number = title_deed.total_transfers;
+
+    title_deed.co_owners.retain(|co_owner| co_owner.authority != deceased);
+    share_equally(&mut title_deed.co_owners);
+    if title_deed.owner.authority == deceased {
+        title_deed.owner = (*ctx.accounts.survivor).clone();
+    }
+    title_deed.authority = title_deed.owner.authority;
+    if title_deed.co_owners.len() == 1 {
+        title_deed.tenure = Tenure::Sole;
+    }
+
+    ctx.accounts.ownership_history.set_inner(OwnershipHistory {
+        title_deed: title_deed_key,
+        previous_owner: deceased,
+        current_owner: title_deed.owner.authority,
+        transferred_at: now,
+        transfer_type: TransferType::Survivorship,
+        share_bps,
+        co_owners: title_deed.co_owners.clone(),
+        parent_titles: Vec::new(),
+        sequence_number,
+        bump: ctx.bumps.ownership_history,
+    });
+
+    msg!(
+        "Joint tenant {} of title deed {} deceased, share passed to {} surviving joint tenants (sequence: {})",
+        deceased,
+        title_deed.title_number,
+        title_deed.co_owners.len(),
+        sequence_number
+    );
+    Ok(())
+}
+
+// the death is recorded against the deceased's wallet by the first of their titles to pass on,
+// so a second user account held by the same wallet cannot be used to deal either
+fn record_death<'info>(
+    registrar: &Signer<'info>,
+    death_record: &UncheckedAccount<'info>,
+    system_program: &Program<'info, System>,
+    program_id: &Pubkey,
+    deceased: Pubkey,
+    bump: u8,
+    recorded_at: i64,
+) -> Result<()> {
+    if death_record.owner == program_id {
+        return Ok(());
+    }
+    create_program_account(
+        &registrar.to_account_info(),
+        &death_record.to_account_info(),
+        &system_program.to_account_info(),
+        program_id,
+        8 + DeathRecord::INIT_SPACE,
+        &[b"death_record", deceased.as_ref(), &[bump]],
+    )?;
+
+    let mut death_record_data = death_record.try_borrow_mut_data()?;
+    DeathRecord {
+        authority: deceased,
+        recorded_by: registrar.key(),
+        recorded_at,
+        bump,
+    }
+    .try_serialize(&mut &mut death_record_data[..])
+}
+
+// joint tenants hold equal shares - any rounding remainder sits with the first listed
+fn share_equally(co_owners: &mut [CoOwner]) {
+    let count = co_owners.len() as u16;
+    let equal_share = TOTAL_SHARE_BPS / count;
+    for co_owner in co_owners.iter_mut() {
+        co_owner.share_bps = equal_share;
+    }
+    co_owners[0].share_bps = TOTAL_SHARE_BPS - equal_share * (count - 1);
+}
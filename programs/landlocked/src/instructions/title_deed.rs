@@ -2,13 +2,29 @@ use anchor_lang::prelude::*;
 use anchor_lang::AccountDeserialize;
 
 use crate::{
-    contexts::{AuthorizeEscrow, AssignTitleDeedToOwner, MarkTitleForSale, SearchTitleDeedByNumber},
+    contexts::{
+        AuthorizeEscrow, AssignTitleDeedToOwner, DelistTitle, MarkShareForSale, MarkTitleForSale,
+        SearchTitleDeedByNumber, UnlistShare,
+    },
     error::ProtocolError,
-    state::{AgreementIndex, Registrar, EscrowState, OwnershipHistory, TitleNumberLookup, TransferType},
-    CancelAgreement, CreateEscrow, DepositPaymentToEscrow, MakeAgreement, SignAgreement,
+    state::{
+        Agreement, AgreementIndex, AgreementOutcome, AgreementRecord, Deposit, Escrow, EscrowState,
+        OwnershipHistory, RejectionReason, SearchCertificate, TitleDeed, TransferType,
+    },
+    co_owner_share, create_program_account, in_registrar_jurisdiction, is_registrar, make_sole_owner, move_lamports,
+    pay_off_charges, require_co_owner_signatures, transfer_share,
+    CancelAgreement, CancelEscrow, CloseExpiredAgreement, CreateEscrow, DepositPaymentToEscrow,
+    ExpireEscrow, MakeAgreement, RejectEscrow, SignAgreement, MAX_REASON_LENGTH, SECONDS_PER_DAY,
+    TOTAL_SHARE_BPS,
 };
 
 pub fn mark_title_for_sale_handler(ctx: Context<MarkTitleForSale>, price: u64) -> Result<()> {
+    require_co_owner_signatures(
+        &ctx.accounts.title_deed,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+
     let title_deed_key = ctx.accounts.title_deed.key();
     let title_deed = &mut ctx.accounts.title_deed;
     let title_for_sale = &mut ctx.accounts.title_for_sale;
@@ -21,6 +37,7 @@ pub fn mark_title_for_sale_handler(ctx: Context<MarkTitleForSale>, price: u64) -
     title_for_sale.title_deed = title_deed_key;
     title_for_sale.seller = (*ctx.accounts.seller).clone();
     title_for_sale.sale_price = price;
+    title_for_sale.share_bps = TOTAL_SHARE_BPS;
     title_for_sale.listed_at = clock.unix_timestamp;
     title_for_sale.bump = ctx.bumps.title_for_sale;
 
@@ -33,6 +50,84 @@ pub fn mark_title_for_sale_handler(ctx: Context<MarkTitleForSale>, price: u64) -
     Ok(())
 }
 
+// a co-owner sells part or all of their own share without the other co-owners
+pub fn mark_share_for_sale_handler(
+    ctx: Context<MarkShareForSale>,
+    share_bps: u16,
+    price: u64,
+) -> Result<()> {
+    let title_deed = &mut ctx.accounts.title_deed;
+    require!(
+        share_bps > 0
+            && share_bps < TOTAL_SHARE_BPS
+            && share_bps <= co_owner_share(title_deed, &ctx.accounts.authority.key()),
+        ProtocolError::InvalidShare
+    );
+    // gifts, subdivisions and other dealings on the whole title wait until listed shares are sold
+    title_deed.listed_shares = title_deed
+        .listed_shares
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+
+    let title_for_sale = &mut ctx.accounts.title_for_sale;
+    title_for_sale.title_deed = title_deed.key();
+    title_for_sale.seller = (*ctx.accounts.seller).clone();
+    title_for_sale.sale_price = price;
+    title_for_sale.share_bps = share_bps;
+    title_for_sale.listed_at = Clock::get()?.unix_timestamp;
+    title_for_sale.bump = ctx.bumps.title_for_sale;
+
+    msg!(
+        "{} basis points of title deed {} marked as for sale at {} lamports by seller {}",
+        share_bps,
+        title_deed.title_number,
+        price,
+        ctx.accounts.seller.authority
+    );
+    Ok(())
+}
+
+// the owner takes the title off the market while no agreement is in progress on it,
+// so it can be gifted or otherwise dealt with again
+pub fn delist_title_handler(ctx: Context<DelistTitle>) -> Result<()> {
+    require!(
+        ctx.accounts.agreement_index.owner != ctx.program_id,
+        ProtocolError::AgreementAlreadyExists
+    );
+
+    let title_deed = &mut ctx.accounts.title_deed;
+    title_deed.is_for_sale = false;
+
+    msg!(
+        "Title deed {} delisted by seller {}",
+        title_deed.title_number,
+        ctx.accounts.authority.key()
+    );
+    Ok(())
+}
+
+// a co-owner takes their share off the market while no agreement is in progress on the title
+pub fn unlist_share_handler(ctx: Context<UnlistShare>) -> Result<()> {
+    require!(
+        ctx.accounts.agreement_index.owner != ctx.program_id,
+        ProtocolError::AgreementAlreadyExists
+    );
+
+    let title_deed = &mut ctx.accounts.title_deed;
+    title_deed.listed_shares = title_deed
+        .listed_shares
+        .checked_sub(1)
+        .ok_or(ProtocolError::ArithmeticUnderflow)?;
+
+    msg!(
+        "{} basis points of title deed {} unlisted by seller {}",
+        ctx.accounts.title_for_sale.share_bps,
+        title_deed.title_number,
+        ctx.accounts.authority.key()
+    );
+    Ok(())
+}
+
 pub fn assign_title_deed_to_owner_handler(
     ctx: Context<AssignTitleDeedToOwner>,
     new_owner_address: Pubkey,
@@ -42,11 +137,14 @@ pub fn assign_title_deed_to_owner_handler(
     district_land_registry: String,
     registry_mapsheet_number: u64,
 ) -> Result<()> {
+    // registrar can only register titles in their own district land registries
+    in_registrar_jurisdiction(&ctx.accounts.registrar, &district_land_registry)?;
+
     let title_deed = &mut ctx.accounts.title_deed;
     let clock = Clock::get()?;
 
     // Update ownership and authority
-    title_deed.owner = (*ctx.accounts.owner).clone();
+    make_sole_owner(title_deed, (*ctx.accounts.owner).clone());
     title_deed.authority = new_owner_address;
     title_deed.title_number = title_number.clone();
     title_deed.location = location;
@@ -55,7 +153,21 @@ pub fn assign_title_deed_to_owner_handler(
     title_deed.registry_mapsheet_number = registry_mapsheet_number;
     title_deed.registration_date = clock.unix_timestamp;
     title_deed.is_for_sale = false;
+    title_deed.listed_shares = 0;
     title_deed.total_transfers = 0; // Initial assignment, no transfers yet
+    title_deed.total_agreements = 0;
+    title_deed.in_succession = false;
+    title_deed.retired_at = None;
+    title_deed.parent_titles = Vec::new();
+    title_deed.child_titles = Vec::new();
+    title_deed.total_charges = 0;
+    title_deed.charged_amount = 0;
+    title_deed.total_caveats = 0;
+    title_deed.active_caveats = 0;
+    title_deed.total_leases = 0;
+    title_deed.leases = Vec::new();
+    title_deed.total_easements = 0;
+    title_deed.easements = Vec::new();
     title_deed.bump = ctx.bumps.title_deed;
 
     // Record initial ownership assignment
@@ -65,90 +177,86 @@ pub fn assign_title_deed_to_owner_handler(
     ownership_history.current_owner = new_owner_address;
     ownership_history.transferred_at = clock.unix_timestamp;
     ownership_history.transfer_type = TransferType::InitialAssignment;
+    ownership_history.share_bps = TOTAL_SHARE_BPS;
+    ownership_history.co_owners = title_deed.co_owners.clone();
+    ownership_history.parent_titles = Vec::new();
     ownership_history.sequence_number = 0;
     ownership_history.bump = ctx.bumps.ownership_history;
 
+    // index the title number for search
+    let title_number_lookup = &mut ctx.accounts.title_number_lookup;
+    title_number_lookup.title_number = title_number;
+    title_number_lookup.title_deed = ctx.accounts.title_deed.key();
+    title_number_lookup.bump = ctx.bumps.title_number_lookup;
+
     msg!(
         "Title deed {} assigned to new owner {}",
         ctx.accounts.title_deed.title_number,
         new_owner_address
     );
 
-    // Note: title_number_lookup will be created lazily when someone searches for this title
-
     Ok(())
 }
 
 /// Search handler - returns title deed details for a given title_number
 /// This allows buyers to search through all title deeds on-chain by title_number
-/// Creates the lookup account lazily on first search
+/// Issues a search certificate to the searcher, created lazily on their first search
 pub fn search_title_deed_by_number_handler(ctx: Context<SearchTitleDeedByNumber>) -> Result<()> {
     let title_deed = &ctx.accounts.title_deed;
-    
-    // Derive PDA for lookup account
-    let seeds = &[
-        b"title_number_lookup",
-        title_deed.title_number.as_bytes(),
-    ];
-    let (lookup_pda, bump) = Pubkey::find_program_address(seeds, ctx.program_id);
-    
-    // Validate PDA matches
-    require!(
-        ctx.accounts.title_number_lookup.key() == lookup_pda,
-        ProtocolError::TitleAuthorityMismatch
-    );
-    
-    // Check if account exists and is initialized
-    let is_initialized = {
-        ctx.accounts.title_number_lookup.lamports() > 0
-    }; 
-    
-    let mut title_number_lookup = if is_initialized {
-        // Account exists, deserialize it
-        let account_data = ctx.accounts.title_number_lookup.try_borrow_data()?;
-        TitleNumberLookup::try_deserialize(&mut &account_data[..])?
-    } else {
-        // Account doesn't exist yet - create it using CPI to system program
-        let rent = anchor_lang::solana_program::rent::Rent::get()?;
-        let space = 8 + TitleNumberLookup::INIT_SPACE;
-        let lamports_required = rent.minimum_balance(space);
-        
-        // Create account via CPI
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::create_account(
-                ctx.accounts.authority.key,
-                &lookup_pda,
-                lamports_required,
-                space as u64,
-                ctx.program_id,
-            ),
+    let title_deed_key = title_deed.key();
+    let bump = ctx.bumps.search_certificate;
+    let clock = Clock::get()?;
+
+    // create the certificate on the first search - an address that was merely funded
+    // beforehand is not owned by the program yet and is taken over rather than trusted
+    if ctx.accounts.search_certificate.owner != ctx.program_id {
+        create_program_account(
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.search_certificate.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.program_id,
+            8 + SearchCertificate::INIT_SPACE,
             &[
-                ctx.accounts.authority.to_account_info(),
-                ctx.accounts.title_number_lookup.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[&[
-                b"title_number_lookup",
-                title_deed.title_number.as_bytes(),
+                b"search_certificate",
+                title_deed_key.as_ref(),
+                ctx.accounts.authority.key.as_ref(),
                 &[bump],
-            ]],
+            ],
         )?;
+    }
 
-        // Initialize new account
-        TitleNumberLookup {
-            title_number: title_deed.title_number.clone(),
-            title_deed: title_deed.key(),
-            searched_by: Pubkey::default(),
-            bump,
-        }
+    // Snapshot the title as it stands - repeating a search reissues the certificate
+    let validity_period = (ctx.accounts.protocol_state.config.search_validity_days as i64)
+        .checked_mul(SECONDS_PER_DAY)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+    let leases_in_force: Vec<_> = title_deed
+        .leases
+        .iter()
+        .filter(|lease| lease.term_end > clock.unix_timestamp)
+        .collect();
+    let search_certificate = SearchCertificate {
+        title_deed: title_deed_key,
+        searched_by: ctx.accounts.searched_by.authority,
+        owner: title_deed.owner.authority,
+        is_for_sale: title_deed.is_for_sale,
+        in_escrow: title_deed.authority != title_deed.owner.authority,
+        leases: leases_in_force.iter().map(|lease| lease.lease).collect(),
+        easements: title_deed.easements.clone(),
+        charged_amount: title_deed.charged_amount,
+        total_charges: title_deed.total_charges,
+        active_caveats: title_deed.active_caveats,
+        total_transfers: title_deed.total_transfers,
+        issued_at: clock.unix_timestamp,
+        expires_at: clock
+            .unix_timestamp
+            .checked_add(validity_period)
+            .ok_or(ProtocolError::ArithmeticOverflow)?,
+        bump,
     };
-    
-    // Update the searched_by field to track who performed the search
-    title_number_lookup.searched_by = ctx.accounts.searched_by.authority;
-    
+
     // Serialize back to account
-    let mut account_data = ctx.accounts.title_number_lookup.try_borrow_mut_data()?;
-    title_number_lookup.try_serialize(&mut &mut account_data[..])?;
+    let mut account_data = ctx.accounts.search_certificate.try_borrow_mut_data()?;
+    search_certificate.try_serialize(&mut &mut account_data[..])?;
 
     // Log all title deed properties for the buyer
     msg!(
@@ -163,6 +271,25 @@ pub fn search_title_deed_by_number_handler(ctx: Context<SearchTitleDeedByNumber>
         ctx.accounts.searched_by.last_name,
         ctx.accounts.searched_by.authority
     );
+    for lease in leases_in_force.iter() {
+        msg!("Lease {} in force until {}", lease.lease, lease.term_end);
+    }
+    for easement in title_deed.easements.iter() {
+        msg!("Easement {}", easement);
+    }
+    if title_deed.active_caveats > 0 {
+        msg!(
+            "{} caveats lodged against the title, dealings are frozen",
+            title_deed.active_caveats
+        );
+    }
+    if title_deed.charged_amount > 0 {
+        msg!(
+            "Charged for {} lamports ({} charges registered)",
+            title_deed.charged_amount,
+            title_deed.total_charges
+        );
+    }
 
     // The title_deed account is already loaded in the context
     // Client can read all properties: owner, authority, title_number, location,
@@ -171,19 +298,36 @@ pub fn search_title_deed_by_number_handler(ctx: Context<SearchTitleDeedByNumber>
 }
 
 pub fn make_agreement_handler(ctx: Context<MakeAgreement>, price: u64) -> Result<()> {
+    // the whole title is sold by its registered owner with every co-owner's signature,
+    // a share by the co-owner holding it
+    let share_bps = ctx.accounts.title_for_sale.share_bps;
+    if share_bps == TOTAL_SHARE_BPS {
+        require!(
+            ctx.accounts.title_deed.owner.authority == ctx.accounts.authority.key(),
+            ProtocolError::Unauthorized
+        );
+        require_co_owner_signatures(
+            &ctx.accounts.title_deed,
+            &ctx.accounts.authority.key(),
+            ctx.remaining_accounts,
+        )?;
+    } else {
+        require!(
+            share_bps <= co_owner_share(&ctx.accounts.title_deed, &ctx.accounts.authority.key()),
+            ProtocolError::InvalidShare
+        );
+    }
+
     let agreement = &mut ctx.accounts.agreement;
-    let title_number_lookup = &ctx.accounts.title_number_lookup;
     let clock = Clock::get()?;
 
-    // Validate that the buyer performed the search
-    require!(
-        title_number_lookup.searched_by != Pubkey::default(),
-        ProtocolError::Unauthorized
-    );
-    require!(
-        title_number_lookup.searched_by == ctx.accounts.buyer.authority,
-        ProtocolError::Unauthorized
-    );
+    // Validate that the buyer holds a current search certificate for the title
+    validate_search_certificate(
+        &ctx.accounts.search_certificate,
+        &ctx.accounts.title_deed,
+        &ctx.accounts.buyer.authority,
+        clock.unix_timestamp,
+    )?;
 
     // Derive AgreementIndex PDA
     let title_deed_key = ctx.accounts.title_deed.key();
@@ -200,7 +344,7 @@ pub fn make_agreement_handler(ctx: Context<MakeAgreement>, price: u64) -> Result
     );
 
     // Check if AgreementIndex already exists and has an active agreement
-    let agreement_index_exists = ctx.accounts.agreement_index.lamports() > 0;
+    let agreement_index_exists = ctx.accounts.agreement_index.owner == ctx.program_id;
     
     if agreement_index_exists {
         // Account exists, check if it has an active agreement
@@ -220,36 +364,29 @@ pub fn make_agreement_handler(ctx: Context<MakeAgreement>, price: u64) -> Result
     agreement.buyer = (*ctx.accounts.buyer).clone();
     agreement.title_deed = ctx.accounts.title_deed.key();
     agreement.price = price;
+    agreement.share_bps = share_bps;
     agreement.created_at = clock.unix_timestamp;
     agreement.drafted_by = ctx.accounts.authority.key();
     agreement.drafted_at = clock.unix_timestamp;
+    agreement.expires_at = clock
+        .unix_timestamp
+        .checked_add(
+            (ctx.accounts.protocol_state.config.agreement_validity_days as i64)
+                .checked_mul(SECONDS_PER_DAY)
+                .ok_or(ProtocolError::ArithmeticOverflow)?,
+        )
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
     agreement.bump = ctx.bumps.agreement;
 
     // Create or update AgreementIndex to enforce one agreement per title deed
     if !agreement_index_exists {
-        // Create new AgreementIndex account
-        let rent = anchor_lang::solana_program::rent::Rent::get()?;
-        let space = 8 + AgreementIndex::INIT_SPACE;
-        let lamports_required = rent.minimum_balance(space);
-        
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::create_account(
-                ctx.accounts.authority.key,
-                &agreement_index_pda,
-                lamports_required,
-                space as u64,
-                ctx.program_id,
-            ),
-            &[
-                ctx.accounts.authority.to_account_info(),
-                ctx.accounts.agreement_index.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[&[
-                b"agreement_index",
-                title_deed_key.as_ref(),
-                &[agreement_index_bump],
-            ]],
+        create_program_account(
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.agreement_index.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.program_id,
+            8 + AgreementIndex::INIT_SPACE,
+            &[b"agreement_index", title_deed_key.as_ref(), &[agreement_index_bump]],
         )?;
     }
 
@@ -280,7 +417,7 @@ pub fn make_agreement_handler(ctx: Context<MakeAgreement>, price: u64) -> Result
 }
 
 // land buyer signs the agreement
-pub fn sign_agreement_handler(ctx: Context<SignAgreement>, price: u64) -> Result<()> {
+pub fn sign_agreement_handler(ctx: Context<SignAgreement>, _price: u64) -> Result<()> {
     // ensure that the authority is the buyer
     require!(
         ctx.accounts.agreement.buyer.authority == ctx.accounts.authority.key(),
@@ -290,6 +427,12 @@ pub fn sign_agreement_handler(ctx: Context<SignAgreement>, price: u64) -> Result
     let agreement = &mut ctx.accounts.agreement;
     let clock = Clock::get()?;
 
+    // expired agreements can no longer be signed
+    require!(
+        clock.unix_timestamp <= agreement.expires_at,
+        ProtocolError::AgreementExpired
+    );
+
     // Store buyer signature
     agreement.buyer_confirmation = Some(ctx.accounts.authority.key());
     agreement.buyer_confirmed_at = Some(clock.unix_timestamp);
@@ -302,7 +445,7 @@ pub fn sign_agreement_handler(ctx: Context<SignAgreement>, price: u64) -> Result
     Ok(())
 }
 
-pub fn cancel_agreement_handler(ctx: Context<CancelAgreement>) -> Result<()> {
+pub fn cancel_agreement_handler(ctx: Context<CancelAgreement>, reason: String) -> Result<()> {
     // ensure that the authority is the buyer or seller
     require!(
         ctx.accounts.agreement.seller.authority == ctx.accounts.authority.key() ||
@@ -310,12 +453,57 @@ pub fn cancel_agreement_handler(ctx: Context<CancelAgreement>) -> Result<()> {
         ProtocolError::Unauthorized
     );
 
-    // Todo: how to handle cancellation when in escrow stage?
+    // once an escrow holds the title the sale has to be unwound through cancel_escrow
+    require!(
+        !escrow_in_progress(&ctx.accounts.escrow, ctx.program_id)?,
+        ProtocolError::AgreementInEscrow
+    );
+    require!(reason.len() <= MAX_REASON_LENGTH, ProtocolError::ReasonTooLong);
+
+    // archive the agreement before it is closed
+    let record = agreement_record(
+        &ctx.accounts.agreement,
+        ctx.accounts.agreement.key(),
+        &mut ctx.accounts.title_deed,
+        AgreementOutcome::Cancelled,
+        ctx.accounts.authority.key(),
+        reason,
+        ctx.bumps.agreement_record,
+    )?;
+    ctx.accounts.agreement_record.set_inner(record);
 
     // close the agreement account
     ctx.accounts.agreement.close(ctx.accounts.authority.to_account_info())?;
 
-    // TODO: store agreement history for audit purposes
+    Ok(())
+}
+
+// permissionless - voids an agreement that was not taken to escrow in time
+pub fn close_expired_agreement_handler(ctx: Context<CloseExpiredAgreement>) -> Result<()> {
+    // agreements in escrow are unwound through the escrow
+    require!(
+        !escrow_in_progress(&ctx.accounts.escrow, ctx.program_id)?,
+        ProtocolError::AgreementInEscrow
+    );
+
+    let record = agreement_record(
+        &ctx.accounts.agreement,
+        ctx.accounts.agreement.key(),
+        &mut ctx.accounts.title_deed,
+        AgreementOutcome::Expired,
+        ctx.accounts.authority.key(),
+        String::new(),
+        ctx.bumps.agreement_record,
+    )?;
+    ctx.accounts.agreement_record.set_inner(record);
+
+    // agreement and agreement index are closed on exit, freeing the title for a new agreement
+    msg!(
+        "Expired agreement {} for title deed {} closed by {}",
+        ctx.accounts.agreement.key(),
+        ctx.accounts.agreement.title_deed,
+        ctx.accounts.authority.key()
+    );
     Ok(())
 }
 
@@ -326,7 +514,20 @@ pub fn create_escrow_handler(ctx: Context<CreateEscrow>) -> Result<()> {
         ctx.accounts.agreement.to_account_info().lamports() > 0,
         ProtocolError::AgreementAlreadyCancelled
     );
+    if ctx.accounts.agreement.share_bps == TOTAL_SHARE_BPS {
+        require_co_owner_signatures(
+            &ctx.accounts.title_deed,
+            &ctx.accounts.authority.key(),
+            ctx.remaining_accounts,
+        )?;
+    }
     let clock = Clock::get()?;
+
+    // agreement is void if it was not taken to escrow in time
+    require!(
+        clock.unix_timestamp <= ctx.accounts.agreement.expires_at,
+        ProtocolError::AgreementExpired
+    );
     
     // buyer and seller must be the ones that appear in the agreement
     confirm_seller(&ctx)?;
@@ -347,6 +548,9 @@ pub fn create_escrow_handler(ctx: Context<CreateEscrow>) -> Result<()> {
     // Get escrow key
     let escrow_key = ctx.accounts.escrow.key();
     let original_authority = ctx.accounts.title_deed.authority;
+    let escrow_period = (ctx.accounts.protocol_state.config.escrow_period_days as i64)
+        .checked_mul(SECONDS_PER_DAY)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
 
     // Initialize escrow account
     let escrow = &mut ctx.accounts.escrow;
@@ -356,6 +560,10 @@ pub fn create_escrow_handler(ctx: Context<CreateEscrow>) -> Result<()> {
     escrow.buyer = ctx.accounts.buyer.authority;
     escrow.state = EscrowState::TitleDeposited; // Set to TitleDeposited since we're transferring authority
     escrow.created_at = clock.unix_timestamp;
+    escrow.deadline = clock
+        .unix_timestamp
+        .checked_add(escrow_period)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
     escrow.completed_at = None;
     escrow.cancelled_at = None;
     escrow.bump = ctx.bumps.escrow;
@@ -377,18 +585,27 @@ pub fn create_escrow_handler(ctx: Context<CreateEscrow>) -> Result<()> {
 // registrar performs due diligence that the process for land transfer was indeed performed
 // by both parties(seller and buyer)
 pub fn authorize_escrow_handler(ctx: Context<AuthorizeEscrow>) -> Result<()> {
-    // ensure authority is a registrar
-    is_registrar(
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
         &ctx.accounts.authority.key(),
         &ctx.accounts.registrar.to_account_info()
     )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
 
     // ensure agreement participants are legitimate
-    // also ensures seller is indeed the owner of the land
-    require!(
-        ctx.accounts.title_deed.owner.authority == ctx.accounts.seller.authority,
-        ProtocolError::Unauthorized
-    );
+    // also ensures seller is indeed the owner of the land, or still holds the share being sold
+    let share_bps = ctx.accounts.agreement.share_bps;
+    if share_bps == TOTAL_SHARE_BPS {
+        require!(
+            ctx.accounts.title_deed.owner.authority == ctx.accounts.seller.authority,
+            ProtocolError::Unauthorized
+        );
+    } else {
+        require!(
+            share_bps <= co_owner_share(&ctx.accounts.title_deed, &ctx.accounts.seller.authority),
+            ProtocolError::InvalidShare
+        );
+    }
 
     require!(
         ctx.accounts.agreement.buyer.authority == ctx.accounts.buyer.authority,
@@ -402,11 +619,13 @@ pub fn authorize_escrow_handler(ctx: Context<AuthorizeEscrow>) -> Result<()> {
         ProtocolError::TitleNotMarkedForSale
     );
 
-    // ensure buyer performed a search on the land
-    require!(
-        ctx.accounts.title_number_lookup.searched_by == ctx.accounts.buyer.authority,
-        ProtocolError::Unauthorized
-    );
+    // ensure buyer performed a search on the land that is still valid
+    validate_search_certificate(
+        &ctx.accounts.search_certificate,
+        &ctx.accounts.title_deed,
+        &ctx.accounts.buyer.authority,
+        Clock::get()?.unix_timestamp,
+    )?;
 
     // ensure buyer signed the agreement
     require!(
@@ -414,10 +633,22 @@ pub fn authorize_escrow_handler(ctx: Context<AuthorizeEscrow>) -> Result<()> {
         ProtocolError::AgreementNotSignedByBuyer
     );
 
+    // registrar must act before the escrow deadline
+    require!(
+        Clock::get()?.unix_timestamp <= ctx.accounts.escrow.deadline,
+        ProtocolError::EscrowExpired
+    );
+
     // if all checks pass, transfer title to buyer(authority and details)
     // transfer title deed authority from escrow to buyer
     let title_deed = &mut ctx.accounts.title_deed;
-    let previous_owner = title_deed.owner.authority; // Store previous owner before transfer
+    let previous_owner = ctx.accounts.seller.authority; // Store previous owner before transfer
+    // proceeds of a whole-title sale are split between the co-owners, a share is paid to its seller
+    let co_owners = if share_bps == TOTAL_SHARE_BPS {
+        title_deed.co_owners.clone()
+    } else {
+        Vec::new()
+    };
     
     // Increment transfer counter first (this is the sequence number for the new history entry)
     title_deed.total_transfers = title_deed.total_transfers
@@ -426,9 +657,17 @@ pub fn authorize_escrow_handler(ctx: Context<AuthorizeEscrow>) -> Result<()> {
     
     let sequence_number = title_deed.total_transfers; // Sequence number for new history entry
     
-    title_deed.authority = ctx.accounts.buyer.authority;
-    title_deed.owner = (*ctx.accounts.buyer).clone(); // Transfer ownership to buyer
-    title_deed.is_for_sale = false;
+    transfer_share(title_deed, &previous_owner, (*ctx.accounts.buyer).clone(), share_bps)?; // Transfer ownership to buyer
+    if share_bps == TOTAL_SHARE_BPS {
+        title_deed.is_for_sale = false;
+        // the new owner starts with no share listings
+        title_deed.listed_shares = 0;
+    } else {
+        // the share listing is closed on exit
+        title_deed.listed_shares = title_deed.listed_shares
+            .checked_sub(1)
+            .ok_or(ProtocolError::ArithmeticUnderflow)?;
+    }
     
     // Derive ownership history PDA using incremented total_transfers
     let title_deed_key = title_deed.key();
@@ -448,30 +687,18 @@ pub fn authorize_escrow_handler(ctx: Context<AuthorizeEscrow>) -> Result<()> {
         ProtocolError::TitleAuthorityMismatch
     );
     
-    // Create ownership history account via CPI
-    let rent = anchor_lang::solana_program::rent::Rent::get()?;
-    let space = 8 + OwnershipHistory::INIT_SPACE;
-    let lamports_required = rent.minimum_balance(space);
-    
-    anchor_lang::solana_program::program::invoke_signed(
-        &anchor_lang::solana_program::system_instruction::create_account(
-            ctx.accounts.authority.key,
-            &ownership_history_pda,
-            lamports_required,
-            space as u64,
-            ctx.program_id,
-        ),
+    create_program_account(
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.ownership_history.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.program_id,
+        8 + OwnershipHistory::INIT_SPACE,
         &[
-            ctx.accounts.authority.to_account_info(),
-            ctx.accounts.ownership_history.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        &[&[
             b"ownership_history",
             title_deed_key.as_ref(),
             &sequence_number.to_le_bytes(),
             &[ownership_history_bump],
-        ]],
+        ],
     )?;
     
     // Initialize ownership history account
@@ -484,6 +711,9 @@ pub fn authorize_escrow_handler(ctx: Context<AuthorizeEscrow>) -> Result<()> {
             current_owner: ctx.accounts.buyer.authority,
             transferred_at: clock.unix_timestamp,
             transfer_type: TransferType::EscrowCompletion,
+            share_bps,
+            co_owners: title_deed.co_owners.clone(),
+            parent_titles: Vec::new(),
             sequence_number,
             bump: ownership_history_bump,
         });
@@ -493,6 +723,9 @@ pub fn authorize_escrow_handler(ctx: Context<AuthorizeEscrow>) -> Result<()> {
     ownership_history.current_owner = ctx.accounts.buyer.authority;
     ownership_history.transferred_at = clock.unix_timestamp;
     ownership_history.transfer_type = TransferType::EscrowCompletion;
+    ownership_history.share_bps = share_bps;
+    ownership_history.co_owners = title_deed.co_owners.clone();
+    ownership_history.parent_titles = Vec::new();
     ownership_history.sequence_number = sequence_number;
     ownership_history.bump = ownership_history_bump;
     
@@ -504,28 +737,57 @@ pub fn authorize_escrow_handler(ctx: Context<AuthorizeEscrow>) -> Result<()> {
         ProtocolError::Unauthorized
     );
 
-    // Transfer funds from deposit account (PDA) to seller's authority (wallet)
+    // Transfer funds from deposit account (PDA) to the sellers' wallets
     // Since deposit account has data, we can't use System Program transfer directly
     // Instead, we manually transfer lamports (program can modify accounts it owns)
     let deposit_account_info = ctx.accounts.deposit.to_account_info();
     let seller_account_info = ctx.accounts.seller_authority.clone();
-    
-    // Get current lamports
-    let deposit_lamports = deposit_account_info.lamports();
-    let seller_lamports = seller_account_info.lamports();
-    
+
     // Transfer the deposit amount (not all lamports, as some is rent exemption)
-    let transfer_amount = ctx.accounts.deposit.amount;
-    
-    // Perform the transfer
-    **deposit_account_info.try_borrow_mut_lamports()? = deposit_lamports
-        .checked_sub(transfer_amount)
+    // lenders are paid off before the sellers see any of it
+    let payoff = pay_off_charges(
+        &mut ctx.accounts.title_deed,
+        &deposit_account_info,
+        ctx.remaining_accounts,
+        ctx.program_id,
+        clock.unix_timestamp,
+    )?;
+    let transfer_amount = ctx.accounts.deposit.amount
+        .checked_sub(payoff)
         .ok_or(ProtocolError::ArithmeticUnderflow)?;
-    **seller_account_info.try_borrow_mut_lamports()? = seller_lamports
-        .checked_add(transfer_amount)
-        .ok_or(ProtocolError::ArithmeticOverflow)?;
+
+    // other co-owners are paid their share pro rata, the registered owner receives the rest
+    let mut seller_amount = transfer_amount;
+    for co_owner in co_owners.iter().filter(|co_owner| co_owner.authority != previous_owner) {
+        let co_owner_account_info = ctx
+            .remaining_accounts
+            .iter()
+            .find(|account| account.key() == co_owner.authority && account.is_writable)
+            .ok_or(ProtocolError::MissingCoOwnerAccount)?;
+        let share = u64::try_from(
+            (transfer_amount as u128) * (co_owner.share_bps as u128) / (TOTAL_SHARE_BPS as u128),
+        )
+        .map_err(|_| ProtocolError::ArithmeticOverflow)?;
+        move_lamports(&deposit_account_info, co_owner_account_info, share)?;
+        seller_amount = seller_amount
+            .checked_sub(share)
+            .ok_or(ProtocolError::ArithmeticUnderflow)?;
+    }
+    move_lamports(&deposit_account_info, &seller_account_info, seller_amount)?;
     
-    // update escrow state to Completed
+    // archive the completed agreement - it is closed on exit
+    let record = agreement_record(
+        &ctx.accounts.agreement,
+        ctx.accounts.agreement.key(),
+        &mut ctx.accounts.title_deed,
+        AgreementOutcome::Completed,
+        ctx.accounts.authority.key(),
+        String::new(),
+        ctx.bumps.agreement_record,
+    )?;
+    ctx.accounts.agreement_record.set_inner(record);
+
+    // update escrow state to Completed - the escrow and deposit are closed on exit
     let escrow = &mut ctx.accounts.escrow;
     escrow.state = EscrowState::Completed;
     escrow.completed_at = Some(clock.unix_timestamp);
@@ -540,7 +802,201 @@ pub fn authorize_escrow_handler(ctx: Context<AuthorizeEscrow>) -> Result<()> {
     Ok(())
 }
 
+// either party may back out while only the title is in escrow. once the buyer has paid
+// only the buyer may cancel - the seller has to wait for the registrar's decision
+pub fn cancel_escrow_handler(ctx: Context<CancelEscrow>, reason: String) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let escrow = &ctx.accounts.escrow;
+    let is_buyer = authority == escrow.buyer;
+    let is_seller = authority == escrow.seller;
+    match escrow.state {
+        EscrowState::TitleDeposited => require!(is_buyer || is_seller, ProtocolError::Unauthorized),
+        EscrowState::PaymentDeposited => require!(is_buyer, ProtocolError::Unauthorized),
+        _ => return err!(ProtocolError::EscrowNotCancellable),
+    }
+    require!(reason.len() <= MAX_REASON_LENGTH, ProtocolError::ReasonTooLong);
+
+    let refund = escrow_refund(escrow, ctx.accounts.deposit.as_deref())?;
+
+    unwind_escrow(&mut ctx.accounts.title_deed);
+    let record = agreement_record(
+        &ctx.accounts.agreement,
+        ctx.accounts.agreement.key(),
+        &mut ctx.accounts.title_deed,
+        AgreementOutcome::Cancelled,
+        authority,
+        reason,
+        ctx.bumps.agreement_record,
+    )?;
+    ctx.accounts.agreement_record.set_inner(record);
+
+    // escrow, agreement, agreement index and deposit are closed on exit, refunding the deposit to the buyer
+    msg!(
+        "Escrow {} cancelled by {}. Title deed authority returned to seller {}, {} lamports refunded to buyer {}",
+        ctx.accounts.escrow.key(),
+        authority,
+        ctx.accounts.escrow.seller,
+        refund,
+        ctx.accounts.escrow.buyer
+    );
+    Ok(())
+}
+
+// permissionless crank - unwinds an escrow once its deadline has passed
+pub fn expire_escrow_handler(ctx: Context<ExpireEscrow>) -> Result<()> {
+    let refund = escrow_refund(&ctx.accounts.escrow, ctx.accounts.deposit.as_deref())?;
+
+    unwind_escrow(&mut ctx.accounts.title_deed);
+    let record = agreement_record(
+        &ctx.accounts.agreement,
+        ctx.accounts.agreement.key(),
+        &mut ctx.accounts.title_deed,
+        AgreementOutcome::Expired,
+        ctx.accounts.authority.key(),
+        String::new(),
+        ctx.bumps.agreement_record,
+    )?;
+    ctx.accounts.agreement_record.set_inner(record);
+
+    // escrow, agreement, agreement index and deposit are closed on exit, refunding the deposit to the buyer
+    msg!(
+        "Escrow {} expired at deadline {}. Title deed authority returned to seller {}, {} lamports refunded to buyer {}",
+        ctx.accounts.escrow.key(),
+        ctx.accounts.escrow.deadline,
+        ctx.accounts.escrow.seller,
+        refund,
+        ctx.accounts.escrow.buyer
+    );
+    Ok(())
+}
+
+// registrar rejects a sale that failed due diligence - the buyer is refunded and the title returned
+pub fn reject_escrow_handler(
+    ctx: Context<RejectEscrow>,
+    reason: RejectionReason,
+    note: String,
+) -> Result<()> {
+    // ensure authority is a registrar with jurisdiction over the title
+    let registrar = is_registrar(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.registrar.to_account_info()
+    )?;
+    in_registrar_jurisdiction(&registrar, &ctx.accounts.title_deed.district_land_registry)?;
+    require!(note.len() <= MAX_REASON_LENGTH, ProtocolError::ReasonTooLong);
+
+    let refund = escrow_refund(&ctx.accounts.escrow, ctx.accounts.deposit.as_deref())?;
+
+    unwind_escrow(&mut ctx.accounts.title_deed);
+    let mut record = agreement_record(
+        &ctx.accounts.agreement,
+        ctx.accounts.agreement.key(),
+        &mut ctx.accounts.title_deed,
+        AgreementOutcome::Rejected,
+        ctx.accounts.authority.key(),
+        note,
+        ctx.bumps.agreement_record,
+    )?;
+    record.rejection_reason = Some(reason);
+    ctx.accounts.agreement_record.set_inner(record);
+
+    // escrow, agreement, agreement index and deposit are closed on exit, refunding the deposit to the buyer
+    msg!(
+        "Escrow {} rejected by registrar {}. Title deed authority returned to seller {}, {} lamports refunded to buyer {}",
+        ctx.accounts.escrow.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.escrow.seller,
+        refund,
+        ctx.accounts.escrow.buyer
+    );
+    Ok(())
+}
+
 // helpers
+// returns the title deed to its registered owner - the escrow is closed on exit, so how it
+// ended is kept on the agreement record instead
+fn unwind_escrow(title_deed: &mut TitleDeed) {
+    title_deed.authority = title_deed.owner.authority;
+}
+
+// amount refunded to the buyer when an escrow is unwound - their deposit must be closed if payment was made
+fn escrow_refund(escrow: &Escrow, deposit: Option<&Deposit>) -> Result<u64> {
+    if escrow.state == EscrowState::PaymentDeposited {
+        require!(deposit.is_some(), ProtocolError::MissingDeposit);
+    }
+    Ok(deposit.map_or(0, |deposit| deposit.amount))
+}
+
+// whether the agreement's escrow holds the title - escrows are closed once they are unwound or completed,
+// so only an account the program owns can be one, whatever lamports were sent to the address
+fn escrow_in_progress(escrow: &AccountInfo, program_id: &Pubkey) -> Result<bool> {
+    if escrow.owner != program_id {
+        return Ok(false);
+    }
+    let escrow = Escrow::try_deserialize(&mut &escrow.try_borrow_data()?[..])?;
+    Ok(matches!(
+        escrow.state,
+        EscrowState::TitleDeposited | EscrowState::PaymentDeposited
+    ))
+}
+
+// builds the audit record for an agreement that has ended and advances the title's agreement counter
+fn agreement_record(
+    agreement: &Agreement,
+    agreement_key: Pubkey,
+    title_deed: &mut TitleDeed,
+    outcome: AgreementOutcome,
+    closed_by: Pubkey,
+    reason: String,
+    bump: u8,
+) -> Result<AgreementRecord> {
+    let sequence_number = title_deed.total_agreements;
+    title_deed.total_agreements = sequence_number
+        .checked_add(1)
+        .ok_or(ProtocolError::ArithmeticOverflow)?;
+
+    Ok(AgreementRecord {
+        title_deed: agreement.title_deed,
+        agreement: agreement_key,
+        seller: agreement.seller.authority,
+        buyer: agreement.buyer.authority,
+        price: agreement.price,
+        share_bps: agreement.share_bps,
+        drafted_at: agreement.drafted_at,
+        buyer_confirmed_at: agreement.buyer_confirmed_at,
+        outcome,
+        closed_by,
+        closed_at: Clock::get()?.unix_timestamp,
+        reason,
+        rejection_reason: None,
+        sequence_number,
+        bump,
+    })
+}
+
+fn validate_search_certificate(
+    search_certificate: &SearchCertificate,
+    title_deed: &TitleDeed,
+    buyer: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    require!(
+        search_certificate.searched_by == *buyer,
+        ProtocolError::Unauthorized
+    );
+    require!(
+        now <= search_certificate.expires_at,
+        ProtocolError::SearchCertificateExpired
+    );
+    // the title must not have changed hands or been charged since the search
+    require!(
+        search_certificate.owner == title_deed.owner.authority
+            && search_certificate.total_transfers == title_deed.total_transfers
+            && search_certificate.total_charges == title_deed.total_charges,
+        ProtocolError::SearchCertificateStale
+    );
+    Ok(())
+}
+
 fn confirm_seller(ctx: &Context<CreateEscrow>) -> Result<()> {
     // ensure that the authority is the seller
     require!(
@@ -560,29 +1016,6 @@ fn confirm_buyer(ctx: &Context<CreateEscrow>) -> Result<()> {
     Ok(())
 }
 
-pub fn is_registrar(authority: &Pubkey, registrar_account: &AccountInfo) -> Result<Registrar> {
-    // Check account exists
-    require!(
-        registrar_account.lamports() > 0,
-        ProtocolError::InvalidRegistrar
-    );
-    
-    // Deserialize registrar
-    let registrar = Registrar::try_deserialize(&mut &registrar_account.data.borrow()[..])?;
-    
-    // Verify it's active and matches authority
-    require!(
-        registrar.is_active,
-        ProtocolError::InvalidRegistrar
-    );
-    require!(
-        registrar.authority == *authority,
-        ProtocolError::InvalidRegistrar
-    );
-    
-    Ok(registrar)
-}
-
 pub fn deposit_payment_to_escrow_handler(
     ctx: Context<DepositPaymentToEscrow>,
     amount: u64,
@@ -595,6 +1028,12 @@ pub fn deposit_payment_to_escrow_handler(
         ProtocolError::EscrowNotReadyForPayment
     );
 
+    // escrows past their deadline can only be expired
+    require!(
+        clock.unix_timestamp <= ctx.accounts.escrow.deadline,
+        ProtocolError::EscrowExpired
+    );
+
     // Verify payment amount matches agreement price
     require!(
         amount == ctx.accounts.agreement.price,
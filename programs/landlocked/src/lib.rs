@@ -13,94 +13,290 @@ pub use state::*;
 
 declare_id!("39fc7eg4u6F3S4Y3MaWn6HSSFNrHMYNgGwy6tW5Dh3QQ");
 
-#[program]
-pub mod landlocked {
+// the IDL instructions generated by `#[program]` still call the deprecated `AccountInfo::realloc`,
+// so the program is kept in its own module where that can be allowed
+#[allow(deprecated)]
+mod landlocked_program {
     use super::*;
 
-    pub fn initialize(ctx: Context<InitializeLandRegistry>, admins: Vec<Pubkey>) -> Result<()> {
-        initialize::handler(ctx, admins)
-    }
+    #[program]
+    pub mod landlocked {
+        use super::*;
 
-    pub fn confirm_admin_account(ctx: Context<ConfirmAdminAccount>) -> Result<()> {
-        confirm_admin_account::handler(ctx)
-    }
+        pub fn initialize(ctx: Context<InitializeLandRegistry>, admins: Vec<Pubkey>) -> Result<()> {
+            initialize::handler(ctx, admins)
+        }
 
-    pub fn add_registrar(
-        ctx: Context<AddRegistrar>,
-        address: Pubkey,
-        first_name: String,
-        last_name: String,
-        id_number: String,
-    ) -> Result<()> {
-        registrar::add_registrar_handler(ctx, address, first_name, last_name, id_number)
-    }
+        pub fn confirm_admin_account(ctx: Context<ConfirmAdminAccount>) -> Result<()> {
+            confirm_admin_account::handler(ctx)
+        }
 
-    pub fn confirm_registrar_account(ctx: Context<ConfirmRegistrarAccount>) -> Result<()> {
-        registrar::confirm_registrar_handler(ctx)
-    }
+        pub fn create_proposal(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+            proposal::create_proposal_handler(ctx, action)
+        }
 
-    pub fn create_user_account(
-        ctx: Context<CreateUserAccount>,
-        first_name: String,
-        last_name: String,
-        id_number: String,
-        phone_number: String,
-    ) -> Result<()> {
-        user::create_user_account_handler(ctx, first_name, last_name, id_number, phone_number)
-    }
+        pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+            proposal::approve_proposal_handler(ctx)
+        }
 
-    pub fn mark_title_for_sale(ctx: Context<MarkTitleForSale>, price: u64) -> Result<()> {
-        title_deed::mark_title_for_sale_handler(ctx, price)
-    }
+        pub fn add_admin(ctx: Context<AddAdmin>) -> Result<()> {
+            admin::add_admin_handler(ctx)
+        }
 
-    pub fn assign_title_deed_to_owner(
-        ctx: Context<AssignTitleDeedToOwner>,
-        new_owner_address: Pubkey,
-        title_number: String,
-        location: String,
-        acreage: f64,
-        district_land_registry: String,
-        registry_mapsheet_number: u64,
-    ) -> Result<()> {
-        title_deed::assign_title_deed_to_owner_handler(
-            ctx,
-            new_owner_address,
-            title_number,
-            location,
-            acreage,
-            district_land_registry,
-            registry_mapsheet_number,
-        )
-    }
+        pub fn remove_admin(ctx: Context<RemoveAdmin>) -> Result<()> {
+            admin::remove_admin_handler(ctx)
+        }
 
-    pub fn search_title_deed_by_number(ctx: Context<SearchTitleDeedByNumber>, title_number: String) -> Result<()> {
-        title_deed::search_title_deed_by_number_handler(ctx)
-    }
+        pub fn rotate_admin(ctx: Context<RotateAdmin>) -> Result<()> {
+            admin::rotate_admin_handler(ctx)
+        }
 
-    pub fn make_agreement(ctx: Context<MakeAgreement>, price: u64) -> Result<()> {
-        title_deed::make_agreement_handler(ctx, price)
-    }
+        pub fn pause_protocol(ctx: Context<PauseProtocol>) -> Result<()> {
+            protocol::pause_protocol_handler(ctx)
+        }
 
-    pub fn sign_agreement(ctx: Context<SignAgreement>, price: u64) -> Result<()> {
-        title_deed::sign_agreement_handler(ctx, price)
-    }
+        pub fn unpause_protocol(ctx: Context<UnpauseProtocol>) -> Result<()> {
+            protocol::unpause_protocol_handler(ctx)
+        }
 
-    pub fn cancel_agreement(ctx: Context<CancelAgreement>) -> Result<()> {
-        title_deed::cancel_agreement_handler(ctx)
-    }
+        pub fn update_protocol_config(ctx: Context<UpdateProtocolConfig>) -> Result<()> {
+            protocol::update_protocol_config_handler(ctx)
+        }
 
-    pub fn create_escrow(ctx: Context<CreateEscrow>) -> Result<()> {
-        title_deed::create_escrow_handler(ctx)
-    }
+        pub fn add_registrar(
+            ctx: Context<AddRegistrar>,
+            address: Pubkey,
+            first_name: String,
+            last_name: String,
+            id_number: String,
+        ) -> Result<()> {
+            registrar::add_registrar_handler(ctx, address, first_name, last_name, id_number)
+        }
 
-    pub fn deposit_payment_to_escrow(
-        ctx: Context<DepositPaymentToEscrow>,
-        amount: u64,
-    ) -> Result<()> {
-        title_deed::deposit_payment_to_escrow_handler(ctx, amount)
-    }
+        pub fn confirm_registrar_account(ctx: Context<ConfirmRegistrarAccount>) -> Result<()> {
+            registrar::confirm_registrar_handler(ctx)
+        }
+
+        pub fn suspend_registrar(ctx: Context<SuspendRegistrar>) -> Result<()> {
+            registrar::suspend_registrar_handler(ctx)
+        }
+
+        pub fn reinstate_registrar(ctx: Context<ReinstateRegistrar>) -> Result<()> {
+            registrar::reinstate_registrar_handler(ctx)
+        }
+
+        pub fn revoke_registrar(ctx: Context<RevokeRegistrar>) -> Result<()> {
+            registrar::revoke_registrar_handler(ctx)
+        }
+
+        pub fn update_registrar_districts(ctx: Context<UpdateRegistrarDistricts>) -> Result<()> {
+            registrar::update_registrar_districts_handler(ctx)
+        }
+
+        pub fn create_user_account(
+            ctx: Context<CreateUserAccount>,
+            first_name: String,
+            last_name: String,
+            id_number: String,
+            phone_number: String,
+        ) -> Result<()> {
+            user::create_user_account_handler(ctx, first_name, last_name, id_number, phone_number)
+        }
+
+        pub fn mark_title_for_sale(ctx: Context<MarkTitleForSale>, price: u64) -> Result<()> {
+            title_deed::mark_title_for_sale_handler(ctx, price)
+        }
+
+        pub fn delist_title(ctx: Context<DelistTitle>) -> Result<()> {
+            title_deed::delist_title_handler(ctx)
+        }
+
+        pub fn assign_title_deed_to_owner(
+            ctx: Context<AssignTitleDeedToOwner>,
+            new_owner_address: Pubkey,
+            title_number: String,
+            location: String,
+            acreage: f64,
+            district_land_registry: String,
+            registry_mapsheet_number: u64,
+        ) -> Result<()> {
+            title_deed::assign_title_deed_to_owner_handler(
+                ctx,
+                new_owner_address,
+                title_number,
+                location,
+                acreage,
+                district_land_registry,
+                registry_mapsheet_number,
+            )
+        }
+
+        pub fn search_title_deed_by_number(ctx: Context<SearchTitleDeedByNumber>, _title_number: String) -> Result<()> {
+            title_deed::search_title_deed_by_number_handler(ctx)
+        }
+
+        pub fn make_agreement(ctx: Context<MakeAgreement>, price: u64) -> Result<()> {
+            title_deed::make_agreement_handler(ctx, price)
+        }
+
+        pub fn sign_agreement(ctx: Context<SignAgreement>, price: u64) -> Result<()> {
+            title_deed::sign_agreement_handler(ctx, price)
+        }
+
+        pub fn cancel_agreement(ctx: Context<CancelAgreement>, reason: String) -> Result<()> {
+            title_deed::cancel_agreement_handler(ctx, reason)
+        }
+
+        pub fn close_expired_agreement(ctx: Context<CloseExpiredAgreement>) -> Result<()> {
+            title_deed::close_expired_agreement_handler(ctx)
+        }
 
-    pub fn authorize_escrow(ctx: Context<AuthorizeEscrow>) -> Result<()> {
-        title_deed::authorize_escrow_handler(ctx)
+        pub fn create_escrow(ctx: Context<CreateEscrow>) -> Result<()> {
+            title_deed::create_escrow_handler(ctx)
+        }
+
+        pub fn deposit_payment_to_escrow(
+            ctx: Context<DepositPaymentToEscrow>,
+            amount: u64,
+        ) -> Result<()> {
+            title_deed::deposit_payment_to_escrow_handler(ctx, amount)
+        }
+
+        pub fn authorize_escrow(ctx: Context<AuthorizeEscrow>) -> Result<()> {
+            title_deed::authorize_escrow_handler(ctx)
+        }
+
+        pub fn cancel_escrow(ctx: Context<CancelEscrow>, reason: String) -> Result<()> {
+            title_deed::cancel_escrow_handler(ctx, reason)
+        }
+
+        pub fn expire_escrow(ctx: Context<ExpireEscrow>) -> Result<()> {
+            title_deed::expire_escrow_handler(ctx)
+        }
+
+        pub fn reject_escrow(
+            ctx: Context<RejectEscrow>,
+            reason: RejectionReason,
+            note: String,
+        ) -> Result<()> {
+            title_deed::reject_escrow_handler(ctx, reason, note)
+        }
+
+        pub fn initiate_gift_transfer(ctx: Context<InitiateGiftTransfer>, share_bps: u16) -> Result<()> {
+            gift::initiate_gift_transfer_handler(ctx, share_bps)
+        }
+
+        pub fn accept_gift_transfer(ctx: Context<AcceptGiftTransfer>) -> Result<()> {
+            gift::accept_gift_transfer_handler(ctx)
+        }
+
+        pub fn approve_gift_transfer(ctx: Context<ApproveGiftTransfer>) -> Result<()> {
+            gift::approve_gift_transfer_handler(ctx)
+        }
+
+        pub fn cancel_gift_transfer(ctx: Context<CancelGiftTransfer>) -> Result<()> {
+            gift::cancel_gift_transfer_handler(ctx)
+        }
+
+        pub fn record_succession(
+            ctx: Context<RecordSuccession>,
+            court_grant_reference: String,
+            administrator: Pubkey,
+            beneficiaries: Vec<Pubkey>,
+        ) -> Result<()> {
+            succession::record_succession_handler(ctx, court_grant_reference, administrator, beneficiaries)
+        }
+
+        pub fn transfer_to_beneficiary(ctx: Context<TransferToBeneficiary>, share_bps: u16) -> Result<()> {
+            succession::transfer_to_beneficiary_handler(ctx, share_bps)
+        }
+
+        pub fn record_survivorship(ctx: Context<RecordSurvivorship>) -> Result<()> {
+            succession::record_survivorship_handler(ctx)
+        }
+
+        pub fn register_co_ownership(
+            ctx: Context<RegisterCoOwnership>,
+            tenure: Tenure,
+            co_owners: Vec<CoOwner>,
+        ) -> Result<()> {
+            co_ownership::register_co_ownership_handler(ctx, tenure, co_owners)
+        }
+
+        pub fn mark_share_for_sale(
+            ctx: Context<MarkShareForSale>,
+            share_bps: u16,
+            price: u64,
+        ) -> Result<()> {
+            title_deed::mark_share_for_sale_handler(ctx, share_bps, price)
+        }
+
+        pub fn unlist_share(ctx: Context<UnlistShare>) -> Result<()> {
+            title_deed::unlist_share_handler(ctx)
+        }
+
+        pub fn subdivide_title_deed<'info>(
+            ctx: Context<'_, '_, 'info, 'info, SubdivideTitleDeed<'info>>,
+            parcels: Vec<Parcel>,
+        ) -> Result<()> {
+            subdivision::subdivide_title_deed_handler(ctx, parcels)
+        }
+
+        pub fn amalgamate_title_deeds<'info>(
+            ctx: Context<'_, '_, 'info, 'info, AmalgamateTitleDeeds<'info>>,
+            title_number: String,
+        ) -> Result<()> {
+            amalgamation::amalgamate_title_deeds_handler(ctx, title_number)
+        }
+
+        pub fn register_charge(ctx: Context<RegisterCharge>, amount: u64) -> Result<()> {
+            charge::register_charge_handler(ctx, amount)
+        }
+
+        pub fn discharge_charge(ctx: Context<DischargeCharge>) -> Result<()> {
+            charge::discharge_charge_handler(ctx)
+        }
+
+        pub fn lodge_caveat(
+            ctx: Context<LodgeCaveat>,
+            kind: CaveatKind,
+            reason: String,
+            expires_at: Option<i64>,
+        ) -> Result<()> {
+            caveat::lodge_caveat_handler(ctx, kind, reason, expires_at)
+        }
+
+        pub fn withdraw_caveat(ctx: Context<WithdrawCaveat>) -> Result<()> {
+            caveat::withdraw_caveat_handler(ctx)
+        }
+
+        pub fn remove_caveat(ctx: Context<RemoveCaveat>, reason: String) -> Result<()> {
+            caveat::remove_caveat_handler(ctx, reason)
+        }
+
+        pub fn lapse_caveat(ctx: Context<LapseCaveat>) -> Result<()> {
+            caveat::lapse_caveat_handler(ctx)
+        }
+
+        pub fn register_lease(
+            ctx: Context<RegisterLease>,
+            lessee: Pubkey,
+            term_start: i64,
+            term_end: i64,
+            rent: u64,
+            permitted_use: String,
+        ) -> Result<()> {
+            lease::register_lease_handler(ctx, lessee, term_start, term_end, rent, permitted_use)
+        }
+
+        pub fn register_easement(
+            ctx: Context<RegisterEasement>,
+            kind: EasementKind,
+            description: String,
+        ) -> Result<()> {
+            easement::register_easement_handler(ctx, kind, description)
+        }
     }
 }
+
+pub use landlocked_program::*;
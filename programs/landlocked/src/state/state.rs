@@ -7,9 +7,20 @@ pub struct ProtocolState {
     #[max_len(5)]
     pub admins: Vec<Pubkey>,
     pub is_paused: bool, // pause the protocol for maintenance or security issues
+    pub proposal_count: u64, // total proposals created, used to derive the next proposal PDA
+    pub config: ProtocolConfig,
     pub bump: u8,
 }
 
+/// Protocol settings that can only be changed through an approved proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct ProtocolConfig {
+    pub approval_threshold: u8, // number of admin approvals required to execute a proposal
+    pub search_validity_days: u16, // days a search certificate remains valid
+    pub escrow_period_days: u16, // days an escrow stays open before it can be expired
+    pub agreement_validity_days: u16, // days the parties have to take an agreement to escrow
+}
+
 /// SuperAdmin accounts are a limited set of accounts that are used to manage the system.
 #[account]
 #[derive(InitSpace)]
@@ -24,6 +35,46 @@ pub struct Admin {
     pub bump: u8,
 }
 
+/// Privileged action that must be approved by a quorum of admins before it is executed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ProposalAction {
+    AddAdmin { admin: Pubkey },
+    RemoveAdmin { admin: Pubkey },
+    RotateAdmin { old_admin: Pubkey, new_admin: Pubkey },
+    AddRegistrar {
+        registrar: Pubkey,
+        #[max_len(5, 100)]
+        districts: Vec<String>,
+    },
+    SuspendRegistrar { registrar: Pubkey, reason: SuspensionReason },
+    ReinstateRegistrar { registrar: Pubkey },
+    RevokeRegistrar { registrar: Pubkey },
+    UpdateRegistrarDistricts {
+        registrar: Pubkey,
+        #[max_len(5, 100)]
+        districts: Vec<String>,
+    },
+    UpdateConfig { config: ProtocolConfig },
+    PauseProtocol,
+    UnpauseProtocol,
+}
+
+/// Proposal drafted by an admin and approved by other admins in ProtocolState.admins
+/// PDA: [b"proposal", id.to_le_bytes()]
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    #[max_len(5)]
+    pub approvals: Vec<Pubkey>, // admins that approved the proposal (proposer included)
+    pub created_at: i64,
+    pub expires_at: i64, // no approvals or execution after this
+    pub executed_at: Option<i64>,
+    pub bump: u8,
+}
+
 /// Registrar accounts manage the land registry, land records and processes like land transfers
 #[account]
 #[derive(InitSpace)]
@@ -36,10 +87,24 @@ pub struct Registrar {
     pub id_number: String,
     pub authority: Pubkey,
     pub added_by: Pubkey,
-    pub is_active: bool,
+    #[max_len(5, 100)]
+    pub districts: Vec<String>, // district land registries under the registrar's jurisdiction
+    pub is_active: bool, // set once the registrar confirms their account
+    pub suspended_at: Option<i64>,
+    pub suspension_reason: Option<SuspensionReason>,
+    pub revoked_at: Option<i64>, // revocation is permanent
     pub bump: u8,
 }
 
+/// Reason code recorded when admins suspend a registrar
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum SuspensionReason {
+    Misconduct,
+    UnderInvestigation,
+    LeftRegistry,
+    Other,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct User {
@@ -53,6 +118,7 @@ pub struct User {
     #[max_len(15)]
     pub phone_number: String,
     pub authority: Pubkey,
+    pub deceased_at: Option<i64>, // set when a registrar records the user's death, freezing all their dealings
 }
 
 /// Claim account to ensure id_number uniqueness globally
@@ -65,6 +131,7 @@ pub struct IdNumberClaim {
     pub bump: u8,
 }
 
+/// PDA: [b"title_deed", title_number.as_bytes()]
 #[account]
 #[derive(InitSpace)]
 pub struct TitleDeed {
@@ -80,31 +147,107 @@ pub struct TitleDeed {
     pub registration_date: i64,
     pub registry_mapsheet_number: u64,
     pub is_for_sale: bool, // TODO: remove this field
+    pub listed_shares: u8, // co-owners' shares listed for sale, open until they are sold
     pub total_transfers: u64, // total number of ownership transfers for this title deed
+    pub total_agreements: u64, // number of agreements archived for this title deed
+    pub in_succession: bool, // owner is deceased - no dealings until the title passes to a beneficiary
+    pub tenure: Tenure,
+    #[max_len(5)]
+    pub co_owners: Vec<CoOwner>, // every owner of the title, including `owner`
+    pub retired_at: Option<i64>, // set when the title is closed by a subdivision or amalgamation - its authority is cleared so no dealings go through
+    #[max_len(5)]
+    pub parent_titles: Vec<Pubkey>, // titles this one was created from
+    #[max_len(5)]
+    pub child_titles: Vec<Pubkey>, // titles created from this one on retirement
+    pub total_charges: u64, // number of charges ever registered against this title deed
+    pub charged_amount: u64, // amount outstanding on undischarged charges
+    pub total_caveats: u64, // number of caveats ever lodged against this title deed
+    pub active_caveats: u16, // caveats still lodged - no dealings while any remain
+    pub total_leases: u64, // number of leases ever registered on this title deed
+    #[max_len(5)]
+    pub leases: Vec<RegisteredLease>, // leases in force, pruned as they run out
+    pub total_easements: u64, // number of easements granted over this title deed
+    #[max_len(5)]
+    pub easements: Vec<Pubkey>, // easements benefiting or burdening this title deed
     pub bump: u8,
 }
 
+/// Lease noted on a title deed until its term ends
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct RegisteredLease {
+    pub lease: Pubkey,
+    pub term_end: i64,
+}
+
+/// Parcel carved out of a title deed on subdivision
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Parcel {
+    pub title_number: String,
+    pub acreage: f64,
+}
+
+/// How a title deed is held by its owners
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Tenure {
+    Sole,
+    JointTenancy, // equal undivided shares
+    TenancyInCommon, // distinct shares of any size
+}
+
+/// Owner's share of a title deed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct CoOwner {
+    pub authority: Pubkey,
+    pub share_bps: u16, // basis points of the whole title
+}
+
 /// Sale listing for a title deed
-/// PDA: [b"title_for_sale", title_deed.key().as_ref()]
+/// PDA: [b"title_for_sale", seller.authority.as_ref(), title_deed.key().as_ref()]
 #[account]
 #[derive(InitSpace)]
 pub struct TitleForSale {
     pub title_deed: Pubkey, // Reference to the title deed being sold
     pub seller: User,       // Seller's user account details
     pub sale_price: u64,    // Price in lamports (smallest unit of SOL)
+    pub share_bps: u16,     // Share of the title being sold, 10000 for the whole title
     pub listed_at: i64,     // Timestamp when listed for sale
     pub bump: u8,
 }
 
 /// Index mapping title_number to TitleDeed account address for search
-/// PDA: [b"title_number_index", title_number.as_bytes()]
+/// Created when the title is registered so a title number can only be registered once
+/// PDA: [b"title_number_lookup", title_number.as_bytes()]
 #[account]
 #[derive(InitSpace)]
 pub struct TitleNumberLookup {
     #[max_len(15)]
     pub title_number: String, // The title number being indexed
     pub title_deed: Pubkey, // Address of the TitleDeed account
-    pub searched_by: Pubkey,  // The authority/pubkey of the user who searched (Pubkey::default() until first search)
+    pub bump: u8,
+}
+
+/// Official search certificate issued to a prospective buyer
+/// Snapshots the state of the title at search time and expires after
+/// ProtocolConfig.search_validity_days
+/// PDA: [b"search_certificate", title_deed.key().as_ref(), searched_by.as_ref()]
+#[account]
+#[derive(InitSpace)]
+pub struct SearchCertificate {
+    pub title_deed: Pubkey,
+    pub searched_by: Pubkey, // authority of the user who searched the title
+    pub owner: Pubkey, // registered owner at search time
+    pub is_for_sale: bool,
+    pub in_escrow: bool, // title authority was held by an escrow at search time
+    #[max_len(5)]
+    pub leases: Vec<Pubkey>, // leases in force at search time
+    #[max_len(5)]
+    pub easements: Vec<Pubkey>, // easements benefiting or burdening the title
+    pub charged_amount: u64, // outstanding charges at search time
+    pub total_charges: u64, // used to detect charges registered after the search
+    pub active_caveats: u16, // caveats and court orders lodged against the title at search time
+    pub total_transfers: u64, // used to detect transfers made after the search
+    pub issued_at: i64,
+    pub expires_at: i64, // search must be repeated once the certificate expires
     pub bump: u8,
 }
 
@@ -115,11 +258,13 @@ pub struct Agreement {
     pub buyer: User,
     pub title_deed: Pubkey,
     pub price: u64,
+    pub share_bps: u16, // share of the title being sold, 10000 for the whole title
     pub created_at: i64,
     pub drafted_by: Pubkey,
     pub buyer_confirmation: Option<Pubkey>,
     pub drafted_at: i64,
     pub buyer_confirmed_at: Option<i64>,
+    pub expires_at: i64, // agreement is void if no escrow is created by this time
     pub bump: u8,
 }
 
@@ -134,7 +279,7 @@ pub struct AgreementIndex {
 }
 
 /// Escrow state enum
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum EscrowState {
     Created, // Escrow created, waiting for title deed deposit
     TitleDeposited, // Title deed authority transferred to escrow
@@ -143,6 +288,16 @@ pub enum EscrowState {
     Cancelled, // Escrow cancelled, authority returned to seller
 }
 
+/// Why a registrar rejected an escrow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RejectionReason {
+    IncompleteDocumentation,
+    OwnershipDispute,
+    IdentityMismatch,
+    SuspectedFraud,
+    Other, // details in the rejection note
+}
+
 /// Escrow account for holding title deed and payment during sale
 /// PDA: [b"escrow", agreement.key().as_ref()]
 #[account]
@@ -154,6 +309,7 @@ pub struct Escrow {
     pub buyer: Pubkey, // Buyer authority
     pub state: EscrowState,
     pub created_at: i64,
+    pub deadline: i64, // escrow can be expired and unwound by anyone after this time
     pub completed_at: Option<i64>,
     pub cancelled_at: Option<i64>,
     pub bump: u8,
@@ -174,8 +330,181 @@ pub struct Deposit {
 pub enum TransferType {
     InitialAssignment, // Initial assignment by registrar
     EscrowCompletion, // Transfer via escrow completion
-    Gift, // TODO
-    Inheritance, // TODO
+    Gift, // Zero-price transfer approved by a registrar
+    Inheritance, // Transfer to a beneficiary of a deceased owner
+    Subdivision, // Title created from a subdivided parent title
+    Amalgamation, // Title created by merging titles of the same owners
+    Survivorship, // Deceased joint tenant's share passing to the surviving joint tenants
+}
+
+/// How an agreement came to an end
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AgreementOutcome {
+    Cancelled, // withdrawn by the buyer or seller
+    Expired, // not taken to escrow, or not authorized before the escrow deadline
+    Completed, // sale authorized by a registrar
+    Rejected, // sale rejected by a registrar
+}
+
+/// Immutable audit record written when an agreement ends
+/// PDA: [b"agreement_record", title_deed.key().as_ref(), sequence_number.to_le_bytes()]
+#[account]
+#[derive(InitSpace)]
+pub struct AgreementRecord {
+    pub title_deed: Pubkey,
+    pub agreement: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub share_bps: u16, // share of the title being sold, TOTAL_SHARE_BPS for the whole title
+    pub drafted_at: i64,
+    pub buyer_confirmed_at: Option<i64>,
+    pub outcome: AgreementOutcome,
+    pub closed_by: Pubkey,
+    pub closed_at: i64,
+    #[max_len(200)]
+    pub reason: String, // why the agreement was cancelled or rejected, empty otherwise
+    pub rejection_reason: Option<RejectionReason>, // set when a registrar rejected the escrow
+    pub sequence_number: u64, // Order of this agreement for the title deed (0-indexed)
+    pub bump: u8,
+}
+
+/// Pending gift of a title deed from its owner to another user
+/// PDA: [b"gift_transfer", title_deed.key().as_ref()]
+#[account]
+#[derive(InitSpace)]
+pub struct GiftTransfer {
+    pub title_deed: Pubkey,
+    pub donor: Pubkey, // owner giving the title away
+    pub recipient: User,
+    pub share_bps: u16, // share of the title given away, 10000 for the whole title
+    pub initiated_at: i64,
+    pub accepted_at: Option<i64>, // set once the recipient accepts the gift
+    pub bump: u8,
+}
+
+/// Succession of a deceased owner's title deed, recorded by a registrar from a court grant
+/// PDA: [b"succession", title_deed.key().as_ref()]
+#[account]
+#[derive(InitSpace)]
+pub struct Succession {
+    pub title_deed: Pubkey,
+    pub deceased: Pubkey, // owner at the time of death
+    pub administrator: Pubkey, // appointed to administer the estate
+    #[max_len(50)]
+    pub court_grant_reference: String,
+    #[max_len(5)]
+    pub beneficiaries: Vec<Pubkey>, // named in the court grant
+    pub recorded_by: Pubkey, // registrar
+    pub recorded_at: i64,
+    pub bump: u8,
+}
+
+/// Death of a user recorded by a registrar, keyed on their wallet so it covers every user account they hold
+/// PDA: [b"death_record", authority.as_ref()]
+#[account]
+#[derive(InitSpace)]
+pub struct DeathRecord {
+    pub authority: Pubkey, // wallet of the deceased
+    pub recorded_by: Pubkey, // registrar
+    pub recorded_at: i64,
+    pub bump: u8,
+}
+
+/// Lender's interest registered against a title deed
+/// PDA: [b"charge", title_deed.key().as_ref(), priority.to_le_bytes()]
+#[account]
+#[derive(InitSpace)]
+pub struct Charge {
+    pub title_deed: Pubkey,
+    pub lender: Pubkey,
+    pub amount: u64, // amount secured on the title
+    pub priority: u64, // order of registration - 1 is paid off first
+    pub registered_by: Pubkey,
+    pub registered_at: i64,
+    pub discharged_at: Option<i64>,
+    pub bump: u8,
+}
+
+/// Kind of restriction lodged against a title deed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum CaveatKind {
+    Caveat, // lodged by a party claiming an interest in the land
+    CourtOrder, // restriction ordered by a court
+}
+
+/// Where a caveat stands
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum CaveatStatus {
+    Lodged,
+    Withdrawn, // by the caveator
+    Removed, // by a registrar
+    Lapsed, // past its expiry
+}
+
+/// Caveat freezing dealings on a title deed, kept as a record once lifted
+/// PDA: [b"caveat", title_deed.key().as_ref(), sequence_number.to_le_bytes()]
+#[account]
+#[derive(InitSpace)]
+pub struct Caveat {
+    pub title_deed: Pubkey,
+    pub caveator: Pubkey,
+    pub kind: CaveatKind,
+    #[max_len(200)]
+    pub reason: String,
+    pub lodged_by: Pubkey, // registrar
+    pub lodged_at: i64,
+    pub expires_at: Option<i64>,
+    pub status: CaveatStatus,
+    pub lifted_by: Option<Pubkey>,
+    pub lifted_at: Option<i64>,
+    #[max_len(200)]
+    pub lifting_reason: String,
+    pub sequence_number: u64, // 1 for the first caveat lodged on the title
+    pub bump: u8,
+}
+
+/// Lease registered as an interest in a title deed - it binds whoever owns the title
+/// PDA: [b"lease", title_deed.key().as_ref(), sequence_number.to_le_bytes()]
+#[account]
+#[derive(InitSpace)]
+pub struct Lease {
+    pub title_deed: Pubkey,
+    pub lessor: Pubkey, // owner who granted the lease
+    pub lessee: Pubkey,
+    pub term_start: i64,
+    pub term_end: i64,
+    pub rent: u64, // lamports per year
+    #[max_len(100)]
+    pub permitted_use: String,
+    pub registered_by: Pubkey, // registrar
+    pub registered_at: i64,
+    pub sequence_number: u64, // 1 for the first lease registered on the title
+    pub bump: u8,
+}
+
+/// Right one parcel enjoys over another
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum EasementKind {
+    RightOfWay, // access road or footpath
+    Wayleave, // utility lines or pipes
+    Other,
+}
+
+/// Easement over a servient title deed for the benefit of a dominant one - it binds both titles' owners
+/// PDA: [b"easement", servient_title.key().as_ref(), sequence_number.to_le_bytes()]
+#[account]
+#[derive(InitSpace)]
+pub struct Easement {
+    pub dominant_title: Pubkey, // parcel that benefits
+    pub servient_title: Pubkey, // parcel that is burdened
+    pub kind: EasementKind,
+    #[max_len(100)]
+    pub description: String,
+    pub registered_by: Pubkey, // registrar
+    pub registered_at: i64,
+    pub sequence_number: u64, // 1 for the first easement granted over the servient title
+    pub bump: u8,
 }
 
 /// Ownership history account tracking each transfer of a title deed
@@ -187,6 +516,11 @@ pub struct OwnershipHistory {
     pub current_owner: Pubkey,
     pub transferred_at: i64,
     pub transfer_type: TransferType,
+    pub share_bps: u16, // share of the title transferred, 10000 for the whole title
+    #[max_len(5)]
+    pub co_owners: Vec<CoOwner>, // ownership table after the transfer
+    #[max_len(5)]
+    pub parent_titles: Vec<Pubkey>, // lineage of a title created from other titles
     pub sequence_number: u64, // Order of this transfer (0-indexed)
     pub bump: u8,
 }
@@ -88,12 +88,14 @@ describe("landlocked", () => {
     firstName: "Kim",
     lastName: "Doe",
     idNumber: "1234567890",
+    districts: ["Nairobi"],
   };
 
   const registrar2Details = {
     firstName: "Job",
     lastName: "Smith",
     idNumber: "94389892343",
+    districts: ["Nairobi", "Kisumu", "Mombasa", "Nakuru"],
   };
 
   let owner1PDA: PublicKey;
@@ -174,23 +176,15 @@ describe("landlocked", () => {
       .signers([owner2])
       .rpc();
 
-    // add registrar2
-    await program.methods
-      .addRegistrar(
-        registrar2.publicKey,
-        registrar2Details.firstName,
-        registrar2Details.lastName,
-        registrar2Details.idNumber
-      )
-      .accounts({
-        authority: admin1.publicKey,
-        registrar: registrar2PDA,
-        admin: admin1PDA,
-        protocolState,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      })
-      .signers([admin1])
-      .rpc();
+    // add registrar2 (approved by admin1 and admin2)
+    await addRegistrar(
+      registrar2.publicKey,
+      registrar2Details.firstName,
+      registrar2Details.lastName,
+      registrar2Details.idNumber,
+      registrar2Details.districts,
+      registrar2PDA
+    );
 
     // Confirm registrar2
     await program.methods
@@ -203,7 +197,7 @@ describe("landlocked", () => {
       .signers([registrar2])
       .rpc();
 
-    titleDeed2PDA = getTitleDeedPDA(owner2.publicKey);
+    titleDeed2PDA = getTitleDeedPDA(owner2Details.titleNumber);
     title2NumberLookupPDA = getTitleNumberLookupPDA(owner2Details.titleNumber);
 
     // assign title deed to owner2
@@ -223,6 +217,8 @@ describe("landlocked", () => {
         titleDeed: titleDeed2PDA,
         owner: owner2PDA,
         ownershipHistory: owner2OwnershipHistoryPDA,
+        titleNumberLookup: title2NumberLookupPDA,
+        protocolState,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([registrar2])
@@ -237,6 +233,10 @@ describe("landlocked", () => {
       assert.equal(protocol.admins[0].toString(), admin1.publicKey.toString());
       assert.equal(protocol.admins[1].toString(), admin2.publicKey.toString());
       assert.equal(protocol.isPaused, false);
+      assert.equal(protocol.config.approvalThreshold, 2);
+      assert.equal(protocol.config.searchValidityDays, 30);
+      assert.equal(protocol.config.escrowPeriodDays, 14);
+      assert.equal(protocol.config.agreementValidityDays, 7);
     });
 
     it("confirms admin account successfully", async () => {
@@ -315,24 +315,279 @@ describe("landlocked", () => {
     });
   });
 
-  describe("registrar accounts", async () => {
+  describe("protocol pause", () => {
+    let pauseProposalPDA: PublicKey;
+
     before(async () => {
+      pauseProposalPDA = await proposeAndApprove({ pauseProtocol: {} });
+    });
+
+    it("cannot pause the protocol if not an admin", async () => {
+      try {
+        await program.methods
+          .pauseProtocol()
+          .accounts({
+            authority: fakeAdmin.publicKey,
+            protocolState,
+            proposal: pauseProposalPDA,
+          })
+          .signers([fakeAdmin])
+          .rpc();
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "InvalidAdmin",
+          `Expected InvalidAdmin error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("allows admins to pause the protocol", async () => {
       await program.methods
-        .addRegistrar(
-          registrar1.publicKey,
-          registrar1Details.firstName,
-          registrar1Details.lastName,
-          registrar1Details.idNumber
-        )
+        .pauseProtocol()
         .accounts({
           authority: admin1.publicKey,
-          registrar: registrar1PDA,
-          admin: admin1PDA,
           protocolState,
-          systemProgram: anchor.web3.SystemProgram.programId,
+          proposal: pauseProposalPDA,
+        })
+        .signers([admin1])
+        .rpc();
+
+      const protocol = await program.account.protocolState.fetch(protocolState);
+      assert.equal(protocol.isPaused, true);
+    });
+
+    it("rejects dealings on title deeds while the protocol is paused", async () => {
+      try {
+        await searchTitleDeedByNumber(
+          owner1,
+          owner2Details.titleNumber,
+          title2NumberLookupPDA,
+          titleDeed2PDA,
+          owner1PDA
+        );
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "ProtocolPaused",
+          `Expected ProtocolPaused error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("allows admins to unpause the protocol", async () => {
+      const unpauseProposalPDA = await proposeAndApprove({
+        unpauseProtocol: {},
+      });
+      await program.methods
+        .unpauseProtocol()
+        .accounts({
+          authority: admin2.publicKey,
+          protocolState,
+          proposal: unpauseProposalPDA,
+        })
+        .signers([admin2])
+        .rpc();
+
+      const protocol = await program.account.protocolState.fetch(protocolState);
+      assert.equal(protocol.isPaused, false);
+    });
+  });
+
+  describe("admin management", () => {
+    const admin3 = anchor.web3.Keypair.generate();
+    const admin4 = anchor.web3.Keypair.generate();
+
+    it("cannot add an admin without the required number of approvals", async () => {
+      const proposalPDA = await createProposal(admin1, {
+        addAdmin: { admin: admin3.publicKey },
+      });
+
+      try {
+        await program.methods
+          .addAdmin()
+          .accounts({
+            authority: admin1.publicKey,
+            protocolState,
+            proposal: proposalPDA,
+          })
+          .signers([admin1])
+          .rpc();
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "ProposalThresholdNotReached",
+          `Expected ProposalThresholdNotReached error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("allows admins to add an admin once the proposal is approved", async () => {
+      const proposalPDA = await proposeAndApprove({
+        addAdmin: { admin: admin3.publicKey },
+      });
+
+      await program.methods
+        .addAdmin()
+        .accounts({
+          authority: admin1.publicKey,
+          protocolState,
+          proposal: proposalPDA,
+        })
+        .signers([admin1])
+        .rpc();
+
+      const protocol = await program.account.protocolState.fetch(protocolState);
+      assert.equal(protocol.admins.length, 3);
+      assert.equal(protocol.admins[2].toString(), admin3.publicKey.toString());
+
+      const proposal = await program.account.proposal.fetch(proposalPDA);
+      assert.ok(proposal.executedAt !== null, "Proposal should be executed");
+      assert.equal(
+        proposal.expiresAt.sub(proposal.createdAt).toNumber(),
+        7 * 24 * 60 * 60,
+        "Proposal should lapse after 7 days"
+      );
+    });
+
+    it("allows admins to rotate an admin's key", async () => {
+      const proposalPDA = await proposeAndApprove({
+        rotateAdmin: {
+          oldAdmin: admin3.publicKey,
+          newAdmin: admin4.publicKey,
+        },
+      });
+
+      await program.methods
+        .rotateAdmin()
+        .accounts({
+          authority: admin1.publicKey,
+          protocolState,
+          proposal: proposalPDA,
+          formerAdmin: admin3.publicKey,
         })
         .signers([admin1])
         .rpc();
+
+      const protocol = await program.account.protocolState.fetch(protocolState);
+      assert.equal(protocol.admins.length, 3);
+      assert.equal(protocol.admins[2].toString(), admin4.publicKey.toString());
+    });
+
+    it("allows admins to remove an admin", async () => {
+      const proposalPDA = await proposeAndApprove({
+        removeAdmin: { admin: admin4.publicKey },
+      });
+
+      await program.methods
+        .removeAdmin()
+        .accounts({
+          authority: admin2.publicKey,
+          protocolState,
+          proposal: proposalPDA,
+          formerAdmin: admin4.publicKey,
+        })
+        .signers([admin2])
+        .rpc();
+
+      const protocol = await program.account.protocolState.fetch(protocolState);
+      assert.equal(protocol.admins.length, 2);
+      assert.ok(
+        !protocol.admins.some(
+          (admin) => admin.toString() === admin4.publicKey.toString()
+        ),
+        "admin4 should no longer be an admin"
+      );
+    });
+
+    it("rejects an approval threshold greater than the number of admins", async () => {
+      const proposalPDA = await proposeAndApprove({
+        updateConfig: {
+          config: {
+            approvalThreshold: 3,
+            searchValidityDays: 30,
+            escrowPeriodDays: 14,
+            agreementValidityDays: 7,
+          },
+        },
+      });
+
+      try {
+        await program.methods
+          .updateProtocolConfig()
+          .accounts({
+            authority: admin1.publicKey,
+            protocolState,
+            proposal: proposalPDA,
+          })
+          .signers([admin1])
+          .rpc();
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "InvalidApprovalThreshold",
+          `Expected InvalidApprovalThreshold error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("rejects a search validity period of zero days", async () => {
+      const proposalPDA = await proposeAndApprove({
+        updateConfig: {
+          config: {
+            approvalThreshold: 2,
+            searchValidityDays: 0,
+            escrowPeriodDays: 14,
+            agreementValidityDays: 7,
+          },
+        },
+      });
+
+      try {
+        await program.methods
+          .updateProtocolConfig()
+          .accounts({
+            authority: admin1.publicKey,
+            protocolState,
+            proposal: proposalPDA,
+          })
+          .signers([admin1])
+          .rpc();
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "InvalidSearchValidityPeriod",
+          `Expected InvalidSearchValidityPeriod error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+  });
+
+  describe("registrar accounts", async () => {
+    before(async () => {
+      await addRegistrar(
+        registrar1.publicKey,
+        registrar1Details.firstName,
+        registrar1Details.lastName,
+        registrar1Details.idNumber,
+        registrar1Details.districts,
+        registrar1PDA
+      );
     });
 
     it("allows an admin to add a registrar account", async () => {
@@ -345,9 +600,38 @@ describe("landlocked", () => {
       assert.equal(registrar.firstName, registrar1Details.firstName);
       assert.equal(registrar.lastName, registrar1Details.lastName);
       assert.equal(registrar.idNumber, registrar1Details.idNumber);
+      assert.deepEqual(registrar.districts, registrar1Details.districts);
       assert.equal(registrar.isActive, false); // Should not be active until confirmed
     });
 
+    it("cannot add a registrar account without a district", async () => {
+      const registrar3 = anchor.web3.Keypair.generate();
+      const [registrar3PDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("registrar"), registrar3.publicKey.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await addRegistrar(
+          registrar3.publicKey,
+          "Jane",
+          "Wanjiru",
+          "5566778899",
+          [],
+          registrar3PDA
+        );
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "InvalidRegistrarDistricts",
+          `Expected InvalidRegistrarDistricts error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
     it("cannot add a registrar account if not an admin", async () => {
       // Create a new registrar PDA for this test (different from the one in before hook)
       const registrar2 = anchor.web3.Keypair.generate();
@@ -356,6 +640,10 @@ describe("landlocked", () => {
         program.programId
       );
 
+      const proposalPDA = await proposeAndApprove({
+        addRegistrar: { registrar: registrar2.publicKey, districts: ["Kisumu"] },
+      });
+
       try {
         await program.methods
           .addRegistrar(registrar2.publicKey, "Jane", "Smith", "0987654321")
@@ -364,6 +652,7 @@ describe("landlocked", () => {
             registrar: registrar2PDA,
             admin: admin1PDA, // Use existing admin account
             protocolState,
+            proposal: proposalPDA,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([fakeAdmin])
@@ -407,17 +696,14 @@ describe("landlocked", () => {
 
       await airdrop(registrar2.publicKey, 100_000_000);
 
-      await program.methods
-        .addRegistrar(registrar2.publicKey, "Jane", "Smith", "0987654321")
-        .accounts({
-          authority: admin1.publicKey,
-          registrar: registrar2PDA,
-          admin: admin1PDA,
-          protocolState,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        })
-        .signers([admin1])
-        .rpc();
+      await addRegistrar(
+        registrar2.publicKey,
+        "Jane",
+        "Smith",
+        "0987654321",
+        ["Kisumu"],
+        registrar2PDA
+      );
 
       try {
         await program.methods
@@ -470,44 +756,218 @@ describe("landlocked", () => {
         );
       }
     });
-  });
 
-  describe("user accounts", () => {
-    it("allows a user to create a user account", async () => {
-      const user = await program.account.user.fetch(owner1PDA);
-      assert.equal(user.authority.toString(), owner1.publicKey.toString());
-      assert.equal(user.firstName, owner1Details.firstName);
-      assert.equal(user.lastName, owner1Details.lastName);
-      assert.equal(user.idNumber, owner1Details.idNumber);
-      assert.equal(user.phoneNumber, owner1Details.phoneNumber);
-    });
+    it("allows admins to suspend a registrar", async () => {
+      const proposalPDA = await proposeAndApprove({
+        suspendRegistrar: {
+          registrar: registrar1.publicKey,
+          reason: { underInvestigation: {} },
+        },
+      });
 
-    it("rejects duplicate id_number", async () => {
-      const user2 = anchor.web3.Keypair.generate();
-      await airdrop(user2.publicKey, 100_000_000);
+      await program.methods
+        .suspendRegistrar()
+        .accounts({
+          authority: admin1.publicKey,
+          registrar: registrar1PDA,
+          protocolState,
+          proposal: proposalPDA,
+        })
+        .signers([admin1])
+        .rpc();
 
-      const user2PDA = getUserAddress(owner1Details.idNumber, user2.publicKey); // Same id_number as user1
-      const idNumberClaimPDA2 = getIdNumberClaimPDA(owner1Details.idNumber);
+      const registrar = await program.account.registrar.fetch(registrar1PDA);
+      assert.ok(registrar.suspendedAt !== null, "Registrar should be suspended");
+      assert.ok(
+        "underInvestigation" in registrar.suspensionReason,
+        "Suspension reason should be recorded"
+      );
+    });
 
+    it("does not allow a suspended registrar to assign title deeds", async () => {
       try {
-        await program.methods
-          .createUserAccount(
-            "Jane",
-            "Smith",
-            owner1Details.idNumber, // Duplicate id_number
-            "0987654321"
-          )
-          .accounts({
-            authority: user2.publicKey,
-            user: user2PDA,
-            idNumberClaim: idNumberClaimPDA2,
-            systemProgram: anchor.web3.SystemProgram.programId,
-          })
-          .signers([user2])
-          .rpc();
-
-        throw new Error("Should have rejected duplicate id_number");
-      } catch (error: any) {
+        await assignTitleDeedToOwner(
+          registrar1,
+          registrar1PDA,
+          owner1.publicKey,
+          owner1Details.titleNumber,
+          owner1Details.location,
+          owner1Details.acreage,
+          owner1Details.districtLandRegistry,
+          owner1Details.registryMapsheetNumber,
+          getTitleDeedPDA(owner1Details.titleNumber),
+          owner1PDA
+        );
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "RegistrarSuspended",
+          `Expected RegistrarSuspended error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("allows admins to reinstate a suspended registrar", async () => {
+      const proposalPDA = await proposeAndApprove({
+        reinstateRegistrar: { registrar: registrar1.publicKey },
+      });
+
+      await program.methods
+        .reinstateRegistrar()
+        .accounts({
+          authority: admin1.publicKey,
+          registrar: registrar1PDA,
+          protocolState,
+          proposal: proposalPDA,
+        })
+        .signers([admin1])
+        .rpc();
+
+      const registrar = await program.account.registrar.fetch(registrar1PDA);
+      assert.equal(registrar.suspendedAt, null);
+      assert.equal(registrar.suspensionReason, null);
+    });
+
+    it("allows admins to update a registrar's districts", async () => {
+      const proposalPDA = await proposeAndApprove({
+        updateRegistrarDistricts: {
+          registrar: registrar1.publicKey,
+          districts: ["Nairobi", "Kiambu"],
+        },
+      });
+
+      await program.methods
+        .updateRegistrarDistricts()
+        .accounts({
+          authority: admin1.publicKey,
+          registrar: registrar1PDA,
+          protocolState,
+          proposal: proposalPDA,
+        })
+        .signers([admin1])
+        .rpc();
+
+      const registrar = await program.account.registrar.fetch(registrar1PDA);
+      assert.deepEqual(registrar.districts, ["Nairobi", "Kiambu"]);
+    });
+
+    it("cannot update a registrar's districts to an invalid district", async () => {
+      const proposalPDA = await proposeAndApprove({
+        updateRegistrarDistricts: {
+          registrar: registrar1.publicKey,
+          districts: ["N".repeat(101)],
+        },
+      });
+
+      try {
+        await program.methods
+          .updateRegistrarDistricts()
+          .accounts({
+            authority: admin1.publicKey,
+            registrar: registrar1PDA,
+            protocolState,
+            proposal: proposalPDA,
+          })
+          .signers([admin1])
+          .rpc();
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "InvalidRegistrarDistricts",
+          `Expected InvalidRegistrarDistricts error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("allows admins to permanently revoke a registrar", async () => {
+      const proposalPDA = await proposeAndApprove({
+        revokeRegistrar: { registrar: registrar1.publicKey },
+      });
+
+      await program.methods
+        .revokeRegistrar()
+        .accounts({
+          authority: admin1.publicKey,
+          registrar: registrar1PDA,
+          protocolState,
+          proposal: proposalPDA,
+        })
+        .signers([admin1])
+        .rpc();
+
+      const registrar = await program.account.registrar.fetch(registrar1PDA);
+      assert.ok(registrar.revokedAt !== null, "Registrar should be revoked");
+
+      // a revoked registrar cannot be reinstated
+      const reinstateProposalPDA = await proposeAndApprove({
+        reinstateRegistrar: { registrar: registrar1.publicKey },
+      });
+      try {
+        await program.methods
+          .reinstateRegistrar()
+          .accounts({
+            authority: admin1.publicKey,
+            registrar: registrar1PDA,
+            protocolState,
+            proposal: reinstateProposalPDA,
+          })
+          .signers([admin1])
+          .rpc();
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "RegistrarRevoked",
+          `Expected RegistrarRevoked error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+  });
+
+  describe("user accounts", () => {
+    it("allows a user to create a user account", async () => {
+      const user = await program.account.user.fetch(owner1PDA);
+      assert.equal(user.authority.toString(), owner1.publicKey.toString());
+      assert.equal(user.firstName, owner1Details.firstName);
+      assert.equal(user.lastName, owner1Details.lastName);
+      assert.equal(user.idNumber, owner1Details.idNumber);
+      assert.equal(user.phoneNumber, owner1Details.phoneNumber);
+    });
+
+    it("rejects duplicate id_number", async () => {
+      const user2 = anchor.web3.Keypair.generate();
+      await airdrop(user2.publicKey, 100_000_000);
+
+      const user2PDA = getUserAddress(owner1Details.idNumber, user2.publicKey); // Same id_number as user1
+      const idNumberClaimPDA2 = getIdNumberClaimPDA(owner1Details.idNumber);
+
+      try {
+        await program.methods
+          .createUserAccount(
+            "Jane",
+            "Smith",
+            owner1Details.idNumber, // Duplicate id_number
+            "0987654321"
+          )
+          .accounts({
+            authority: user2.publicKey,
+            user: user2PDA,
+            idNumberClaim: idNumberClaimPDA2,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([user2])
+          .rpc();
+
+        throw new Error("Should have rejected duplicate id_number");
+      } catch (error: any) {
         // Should fail because idNumberClaim account already exists
         assert.ok(
           error.message.includes("already in use") ||
@@ -545,7 +1005,7 @@ describe("landlocked", () => {
       };
       buyer1PDA = getUserAddress(buyer1Details.idNumber, buyer1.publicKey);
       buyer1IdNumberClaimPDA = getIdNumberClaimPDA(buyer1Details.idNumber);
-      titleDeedPDA = getTitleDeedPDA(owner1.publicKey);
+      titleDeedPDA = getTitleDeedPDA(owner1Details.titleNumber);
       titleNumberLookupPDA = getTitleNumberLookupPDA(owner1Details.titleNumber);
       title2ForSalePDA = getTitleForSalePDA(titleDeed2PDA, owner2.publicKey);
 
@@ -591,11 +1051,6 @@ describe("landlocked", () => {
         owner1PDA
       );
 
-      // fetch search result
-      const searchResult = await program.account.titleNumberLookup.fetch(
-        title2NumberLookupPDA
-      );
-
       // owner2 makes agreement
       agreement2PDA = getAgreementPDA(
         owner2.publicKey,
@@ -613,7 +1068,7 @@ describe("landlocked", () => {
         title2ForSalePDA,
         owner2PDA,
         owner1PDA,
-        title2NumberLookupPDA,
+        getSearchCertificatePDA(titleDeed2PDA, owner1.publicKey),
         agreement2PDA,
         agreementIndex2PDA
       );
@@ -642,6 +1097,67 @@ describe("landlocked", () => {
       );
     });
 
+    it("allows an owner to hold multiple title deeds", async () => {
+      const secondTitleNumber = "1234567891";
+      const secondTitleDeedPDA = getTitleDeedPDA(secondTitleNumber);
+
+      await assignTitleDeedToOwner(
+        registrar2,
+        registrar2PDA,
+        owner1.publicKey,
+        secondTitleNumber,
+        "Karen",
+        50,
+        "Nairobi",
+        new BN(98765),
+        secondTitleDeedPDA,
+        owner1PDA
+      );
+
+      const firstTitleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      const secondTitleDeed = await program.account.titleDeed.fetch(
+        secondTitleDeedPDA
+      );
+      assert.equal(
+        firstTitleDeed.owner.authority.toString(),
+        owner1.publicKey.toString()
+      );
+      assert.equal(
+        secondTitleDeed.owner.authority.toString(),
+        owner1.publicKey.toString()
+      );
+      assert.equal(secondTitleDeed.titleNumber, secondTitleNumber);
+    });
+
+    it("does not allow a registrar to assign a title deed outside their districts", async () => {
+      const eldoretOwner = anchor.web3.Keypair.generate();
+      const eldoretTitleNumber = "556677889900";
+
+      try {
+        await assignTitleDeedToOwner(
+          registrar2,
+          registrar2PDA,
+          eldoretOwner.publicKey,
+          eldoretTitleNumber,
+          "Eldoret",
+          100,
+          "Eldoret",
+          new BN(445566),
+          getTitleDeedPDA(eldoretTitleNumber),
+          owner2PDA
+        );
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "OutsideRegistrarJurisdiction",
+          `Expected OutsideRegistrarJurisdiction error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
     it("records ownership history for initial title deed assignment", async () => {
       const ownershipHistoryPDA = getOwnershipHistoryPDA(titleDeedPDA, 0);
       const ownershipHistory = await program.account.ownershipHistory.fetch(
@@ -685,7 +1201,9 @@ describe("landlocked", () => {
           authority: owner1.publicKey, // owner
           titleDeed: titleDeedPDA,
           seller: owner1PDA,
+          deathRecord: getDeathRecordPDA(owner1.publicKey),
           titleForSale: title1ForSalePDA,
+          protocolState,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([owner1])
@@ -713,7 +1231,9 @@ describe("landlocked", () => {
             authority: owner2.publicKey, // not the owner
             titleDeed: titleDeedPDA,
             seller: owner2PDA,
+            deathRecord: getDeathRecordPDA(owner2.publicKey),
             titleForSale: titleForSalePDA,
+            protocolState,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([owner2])
@@ -741,6 +1261,39 @@ describe("landlocked", () => {
       );
       // title2 was marked for sale in the before hook, so isForSale should be true
       assert.equal(titleDeed.isForSale, true);
+
+      // the title number index points at the registered title deed
+      const titleNumberLookup = await program.account.titleNumberLookup.fetch(
+        title2NumberLookupPDA
+      );
+      assert.equal(titleNumberLookup.titleDeed.toString(), titleDeed2PDA.toString());
+    });
+
+    it("does not allow a title number to be registered twice", async () => {
+      const otherOwner = anchor.web3.Keypair.generate();
+
+      try {
+        await assignTitleDeedToOwner(
+          registrar2,
+          registrar2PDA,
+          otherOwner.publicKey,
+          owner2Details.titleNumber,
+          owner2Details.location,
+          owner2Details.acreage,
+          owner2Details.districtLandRegistry,
+          owner2Details.registryMapsheetNumber,
+          titleDeed2PDA,
+          owner1PDA
+        );
+        assert.fail("Expected transaction to fail");
+      } catch (error: any) {
+        // Should fail because the title number is already registered
+        assert.ok(
+          error.message.includes("already in use") ||
+            error.code === 3009, // AccountAlreadyInUse
+          "Expected account already in use error"
+        );
+      }
     });
 
     it("can allow a buyer to make an agreement", async () => {
@@ -756,6 +1309,40 @@ describe("landlocked", () => {
       assert.equal(agreement.titleDeed.toString(), titleDeed2PDA.toString());
       assert.equal(agreement.price.toString(), new BN(1000000000).toString());
       assert.equal(agreement.draftedBy.toString(), owner2.publicKey.toString());
+      assert.equal(
+        agreement.expiresAt.sub(agreement.createdAt).toNumber(),
+        7 * 24 * 60 * 60,
+        "Agreement should expire after the configured number of days"
+      );
+    });
+
+    it("does not allow an agreement to be closed before it expires", async () => {
+      try {
+        await program.methods
+          .closeExpiredAgreement()
+          .accounts({
+            authority: buyer1.publicKey,
+            titleDeed: titleDeed2PDA,
+            agreement: agreement2PDA,
+            agreementRecord: await getNextAgreementRecordPDA(titleDeed2PDA),
+            agreementIndex: agreementIndex2PDA,
+            escrow: getEscrowPDA(agreement2PDA),
+            sellerAuthority: owner2.publicKey,
+            protocolState,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "AgreementNotExpired",
+          `Expected AgreementNotExpired error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
     });
 
     it("does not allow seller to have multiple agreements for the same title deed at the same time", async () => {
@@ -767,7 +1354,9 @@ describe("landlocked", () => {
           authority: buyer1.publicKey,
           titleDeed: titleDeed2PDA,
           titleNumberLookup: title2NumberLookupPDA,
+          searchCertificate: getSearchCertificatePDA(titleDeed2PDA, buyer1.publicKey),
           searchedBy: buyer1PDA,
+          protocolState,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([buyer1])
@@ -789,10 +1378,12 @@ describe("landlocked", () => {
             titleDeed: titleDeed2PDA,
             titleForSale: title2ForSalePDA,
             seller: owner2PDA,
+            deathRecord: getDeathRecordPDA(owner2.publicKey),
             buyer: buyer1PDA,
-            titleNumberLookup: title2NumberLookupPDA,
+            searchCertificate: getSearchCertificatePDA(titleDeed2PDA, buyer1.publicKey),
             agreement: agreement3PDA,
             agreementIndex: agreementIndex3PDA,
+            protocolState,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([owner2])
@@ -809,16 +1400,70 @@ describe("landlocked", () => {
       }
     });
 
+    it("issues a separate search certificate to each prospective buyer", async () => {
+      // both owner1 and buyer1 searched title2
+      const owner1SearchCertificate = await program.account.searchCertificate.fetch(
+        getSearchCertificatePDA(titleDeed2PDA, owner1.publicKey)
+      );
+      const buyer1SearchCertificate = await program.account.searchCertificate.fetch(
+        getSearchCertificatePDA(titleDeed2PDA, buyer1.publicKey)
+      );
+      assert.equal(
+        owner1SearchCertificate.searchedBy.toString(),
+        owner1.publicKey.toString()
+      );
+      assert.equal(
+        buyer1SearchCertificate.searchedBy.toString(),
+        buyer1.publicKey.toString()
+      );
+      // certificates snapshot the title at search time
+      assert.equal(
+        owner1SearchCertificate.owner.toString(),
+        owner2.publicKey.toString()
+      );
+      assert.equal(owner1SearchCertificate.isForSale, true);
+      assert.equal(owner1SearchCertificate.inEscrow, false);
+      assert.equal(
+        owner1SearchCertificate.expiresAt.sub(owner1SearchCertificate.issuedAt).toNumber(),
+        30 * 24 * 60 * 60,
+        "Search certificate should expire after the configured number of days"
+      );
+    });
+
+    it("issues a search certificate at an address that was funded beforehand", async () => {
+      const searchCertificatePDA = getSearchCertificatePDA(titleDeedPDA, buyer1.publicKey);
+      // anyone can send lamports to the certificate address before the buyer searches
+      await airdrop(searchCertificatePDA, 1_000_000);
+
+      await searchTitleDeedByNumber(
+        buyer1,
+        owner1Details.titleNumber,
+        titleNumberLookupPDA,
+        titleDeedPDA,
+        buyer1PDA
+      );
+
+      const searchCertificate = await program.account.searchCertificate.fetch(
+        searchCertificatePDA
+      );
+      assert.equal(searchCertificate.searchedBy.toString(), buyer1.publicKey.toString());
+      assert.equal(searchCertificate.owner.toString(), owner1.publicKey.toString());
+    });
+
     it("does not allow seller to cancel an agreement if not the seller", async () => {
       // the agreement is between owner2(seller) and buyer1(buyer)
       // buyer1 who is not part of the deal tries to cancel the agreement
       try {
         await program.methods
-          .cancelAgreement()
+          .cancelAgreement("No longer interested")
           .accounts({
             authority: buyer1.publicKey,
+            titleDeed: titleDeed2PDA,
             agreement: agreement2PDA,
+            agreementRecord: await getNextAgreementRecordPDA(titleDeed2PDA),
+            escrow: getEscrowPDA(agreement2PDA),
             agreementIndex: agreementIndex2PDA,
+            protocolState,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([buyer1])
@@ -848,17 +1493,33 @@ describe("landlocked", () => {
         agreementBeforeCancellation.buyer.authority.toString(),
         owner1.publicKey.toString()
       );
+      const agreementRecordPDA = await getNextAgreementRecordPDA(titleDeed2PDA);
       await program.methods
-        .cancelAgreement()
+        .cancelAgreement("Found another property")
         .accounts({
           authority: owner1.publicKey,
+          titleDeed: titleDeed2PDA,
           agreement: agreement2PDA,
+          agreementRecord: agreementRecordPDA,
+          escrow: getEscrowPDA(agreement2PDA),
           agreementIndex: agreementIndex2PDA,
+          protocolState,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([owner1])
         .rpc();
 
+      // the cancelled agreement is archived with the reason given
+      const agreementRecord = await program.account.agreementRecord.fetch(
+        agreementRecordPDA
+      );
+      assert.ok("cancelled" in agreementRecord.outcome, "Record should be cancelled");
+      assert.equal(agreementRecord.agreement.toString(), agreement2PDA.toString());
+      assert.equal(agreementRecord.seller.toString(), owner2.publicKey.toString());
+      assert.equal(agreementRecord.buyer.toString(), owner1.publicKey.toString());
+      assert.equal(agreementRecord.closedBy.toString(), owner1.publicKey.toString());
+      assert.equal(agreementRecord.reason, "Found another property");
+
       // Assert that the agreement account no longer exists after cancellation
       try {
         await program.account.agreement.fetch(agreement2PDA);
@@ -962,7 +1623,7 @@ describe("landlocked", () => {
       );
 
       // Set up PDAs for escrow title deed
-      titleDeedPDA = getTitleDeedPDA(escrowSeller.publicKey);
+      titleDeedPDA = getTitleDeedPDA(escrowTitleDetails.titleNumber);
       titleForSalePDA = getTitleForSalePDA(
         titleDeedPDA,
         escrowSeller.publicKey
@@ -1020,7 +1681,7 @@ describe("landlocked", () => {
         titleForSalePDA,
         sellerPDA,
         buyerPDA,
-        titleNumberLookupPDA,
+        getSearchCertificatePDA(titleDeedPDA, escrowBuyer.publicKey),
         agreementPDA,
         agreementIndexPDA
       );
@@ -1109,7 +1770,7 @@ describe("landlocked", () => {
       // Create a new title deed and agreement for this test
       const testTitleNumber = "888777666555";
       const testTitleNumberLookupPDA = getTitleNumberLookupPDA(testTitleNumber);
-      const testTitleDeedPDA = getTitleDeedPDA(testSeller.publicKey);
+      const testTitleDeedPDA = getTitleDeedPDA(testTitleNumber);
       const testTitleForSalePDA = getTitleForSalePDA(
         testTitleDeedPDA,
         testSeller.publicKey
@@ -1123,7 +1784,7 @@ describe("landlocked", () => {
         testTitleNumber,
         "Test Location 2",
         250,
-        "Test Registry 2",
+        "Nairobi",
         new BN(111222333),
         testTitleDeedPDA,
         testSellerPDA
@@ -1164,7 +1825,7 @@ describe("landlocked", () => {
         testTitleForSalePDA,
         testSellerPDA,
         buyerPDA,
-        testTitleNumberLookupPDA,
+        getSearchCertificatePDA(testTitleDeedPDA, escrowBuyer.publicKey),
         testAgreementPDA,
         testAgreementIndexPDA
       );
@@ -1179,11 +1840,15 @@ describe("landlocked", () => {
 
       // Cancel the agreement (buyer cancels it)
       await program.methods
-        .cancelAgreement()
+        .cancelAgreement("Changed my mind")
         .accounts({
           authority: escrowBuyer.publicKey,
+          titleDeed: testTitleDeedPDA,
           agreement: testAgreementPDA,
+          agreementRecord: await getNextAgreementRecordPDA(testTitleDeedPDA),
+          escrow: getEscrowPDA(testAgreementPDA),
           agreementIndex: testAgreementIndexPDA,
+          protocolState,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([escrowBuyer])
@@ -1245,6 +1910,11 @@ describe("landlocked", () => {
       assert.equal(escrow.titleDeed.toString(), titleDeedPDA.toString());
       assert.equal(escrow.seller.toString(), escrowSeller.publicKey.toString());
       assert.equal(escrow.buyer.toString(), escrowBuyer.publicKey.toString());
+      assert.equal(
+        escrow.deadline.sub(escrow.createdAt).toNumber(),
+        14 * 24 * 60 * 60,
+        "Escrow deadline should be the configured number of days after creation"
+      );
 
       // Verify that title deed authority was transferred to escrow
       const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
@@ -1337,7 +2007,7 @@ describe("landlocked", () => {
         );
 
         // Set up PDAs for deposit title deed
-        depositTitleDeedPDA = getTitleDeedPDA(depositSeller.publicKey);
+        depositTitleDeedPDA = getTitleDeedPDA(depositTitleDetails.titleNumber);
         depositTitleForSalePDA = getTitleForSalePDA(
           depositTitleDeedPDA,
           depositSeller.publicKey
@@ -1395,7 +2065,7 @@ describe("landlocked", () => {
           depositTitleForSalePDA,
           depositSellerPDA,
           depositBuyerPDA,
-          depositTitleNumberLookupPDA,
+          getSearchCertificatePDA(depositTitleDeedPDA, depositBuyer.publicKey),
           depositAgreementPDA,
           depositAgreementIndexPDA
         );
@@ -1435,6 +2105,7 @@ describe("landlocked", () => {
               escrow: depositEscrowPDA,
               agreement: depositAgreementPDA,
               deposit: depositPDA,
+              protocolState,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
             .signers([depositBuyer])
@@ -1497,6 +2168,7 @@ describe("landlocked", () => {
               escrow: depositEscrowPDA,
               agreement: depositAgreementPDA,
               deposit: depositPDA,
+              protocolState,
               systemProgram: anchor.web3.SystemProgram.programId,
             })
             .signers([unauthorizedUser])
@@ -1684,7 +2356,7 @@ describe("landlocked", () => {
         );
 
         // Set up PDAs for auth title deed
-        titleDeedPDA = getTitleDeedPDA(seller.publicKey);
+        titleDeedPDA = getTitleDeedPDA(titleDetails.titleNumber);
         titleForSalePDA = getTitleForSalePDA(titleDeedPDA, seller.publicKey);
         titleNumberLookupPDA = getTitleNumberLookupPDA(
           titleDetails.titleNumber
@@ -1740,7 +2412,7 @@ describe("landlocked", () => {
           titleForSalePDA,
           sellerPDA,
           buyerPDA,
-          titleNumberLookupPDA,
+          getSearchCertificatePDA(titleDeedPDA, buyer.publicKey),
           agreementPDA,
           agreementIndexPDA
         );
@@ -1779,8 +2451,11 @@ describe("landlocked", () => {
         // Get balances before authorization
         const sellerBalanceBefore =
           await program.provider.connection.getBalance(seller.publicKey);
-        const depositBalanceBefore =
-          await program.provider.connection.getBalance(depositPDA);
+        // rent of the listing, agreement accounts and escrow is refunded to the seller when they are closed
+        let sellerRefundedRent = 0;
+        for (const account of [titleForSalePDA, agreementPDA, agreementIndexPDA, escrowPDA]) {
+          sellerRefundedRent += await program.provider.connection.getBalance(account);
+        }
 
         // Get title deed before authorization
         const titleDeedBefore = await program.account.titleDeed.fetch(
@@ -1792,6 +2467,10 @@ describe("landlocked", () => {
           titleDeedPDA,
           sequenceNumber + 1
         );
+        const agreementRecordPDA = getAgreementRecordPDA(
+          titleDeedPDA,
+          titleDeedBefore.totalAgreements.toNumber()
+        );
 
         // Registrar authorizes the escrow
         await program.methods
@@ -1805,10 +2484,15 @@ describe("landlocked", () => {
             ownershipHistory: ownershipHistoryPDA,
             titleForSale: titleForSalePDA,
             agreement: agreementPDA,
-            titleNumberLookup: titleNumberLookupPDA,
+            agreementIndex: agreementIndexPDA,
+            agreementRecord: agreementRecordPDA,
+            searchCertificate: getSearchCertificatePDA(titleDeedPDA, buyer.publicKey),
             buyer: buyerPDA,
             seller: sellerPDA,
             sellerAuthority: seller.publicKey, // Seller's wallet to receive funds
+            deathRecord: getDeathRecordPDA(seller.publicKey),
+            buyerAuthority: buyer.publicKey,
+            protocolState,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([registrar2])
@@ -1818,22 +2502,21 @@ describe("landlocked", () => {
         const sellerBalanceAfter = await program.provider.connection.getBalance(
           seller.publicKey
         );
-        const depositBalanceAfter =
-          await program.provider.connection.getBalance(depositPDA);
 
-        // Verify escrow state is Completed
-        const escrow = await program.account.escrow.fetch(escrowPDA);
-        // Anchor enums are objects - check if the 'completed' property exists
-        assert.ok(
-          "completed" in escrow.state,
-          `Escrow should be completed, but state is: ${JSON.stringify(
-            escrow.state
-          )}`
-        );
-        assert.ok(
-          escrow.completedAt !== null,
-          "Escrow should have completion timestamp"
+        // the completed escrow and deposit are closed so the parties can deal on the title again
+        const escrow = await program.provider.connection.getAccountInfo(escrowPDA);
+        assert.equal(escrow, null, "Escrow should be closed");
+        const deposit = await program.provider.connection.getAccountInfo(depositPDA);
+        assert.equal(deposit, null, "Deposit should be closed");
+
+        // the completed agreement is archived and closed
+        const agreementRecord = await program.account.agreementRecord.fetch(
+          agreementRecordPDA
         );
+        assert.ok("completed" in agreementRecord.outcome, "Record should be completed");
+        assert.equal(agreementRecord.closedBy.toString(), registrar2.publicKey.toString());
+        const agreementAfter = await program.provider.connection.getAccountInfo(agreementPDA);
+        assert.equal(agreementAfter, null, "Agreement should be closed");
 
         // Verify title deed ownership transferred to buyer
         const titleDeedAfter = await program.account.titleDeed.fetch(
@@ -1897,325 +2580,2944 @@ describe("landlocked", () => {
         const maxAllowedDifference = 100000; // Allow for transaction fees
 
         assert.ok(
-          Math.abs(sellerBalanceIncrease - depositAmount.toNumber()) <=
-            maxAllowedDifference,
+          Math.abs(
+            sellerBalanceIncrease - depositAmount.toNumber() - sellerRefundedRent
+          ) <= maxAllowedDifference,
           `Seller should receive approximately ${depositAmount.toString()} lamports (actual increase: ${sellerBalanceIncrease})`
         );
 
-        // Verify deposit account balance decreased (funds transferred to seller)
-        assert.ok(
-          depositBalanceAfter < depositBalanceBefore,
-          "Deposit account balance should decrease after transfer"
+
+        // Verify the sale listing was closed
+        const titleForSaleAccount =
+          await program.provider.connection.getAccountInfo(titleForSalePDA);
+        assert.equal(
+          titleForSaleAccount,
+          null,
+          "Title for sale listing should be closed"
         );
       });
+
+      it("does not allow an escrow to be authorized against another title", async () => {
+        const price = new BN(100_000_000); // 0.1 SOL
+        const sale = await setupEscrowSale("405222", "40522", price, true);
+        const otherSale = await setupEscrowSale("405333", "40533", price, true);
+
+        try {
+          await program.methods
+            .authorizeEscrow()
+            .accounts({
+              authority: registrar2.publicKey,
+              registrar: registrar2PDA,
+              escrow: sale.escrowPDA,
+              deposit: sale.depositPDA,
+              titleDeed: otherSale.titleDeedPDA,
+              ownershipHistory: getOwnershipHistoryPDA(otherSale.titleDeedPDA, 1),
+              titleForSale: getTitleForSalePDA(otherSale.titleDeedPDA, otherSale.seller.publicKey),
+              agreement: sale.agreementPDA,
+              agreementIndex: otherSale.agreementIndexPDA,
+              agreementRecord: await getNextAgreementRecordPDA(otherSale.titleDeedPDA),
+              searchCertificate: getSearchCertificatePDA(otherSale.titleDeedPDA, otherSale.buyer.publicKey),
+              buyer: sale.buyerPDA,
+              seller: sale.sellerPDA,
+              sellerAuthority: sale.seller.publicKey,
+              deathRecord: getDeathRecordPDA(sale.seller.publicKey),
+              buyerAuthority: sale.buyer.publicKey,
+              protocolState,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([registrar2])
+            .rpc();
+          assert.fail("Expected transaction to fail");
+        } catch (error) {
+          assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+          const anchorError = error as anchor.AnchorError;
+          assert.equal(
+            anchorError.error?.errorCode?.code,
+            "InvalidTitleDeed",
+            `Expected InvalidTitleDeed error, got: ${anchorError.error?.errorCode?.code}`
+          );
+        }
+
+        // neither title changed hands
+        const titleDeed = await program.account.titleDeed.fetch(sale.titleDeedPDA);
+        assert.equal(titleDeed.authority.toString(), sale.escrowPDA.toString());
+        const otherTitleDeed = await program.account.titleDeed.fetch(otherSale.titleDeedPDA);
+        assert.equal(otherTitleDeed.authority.toString(), otherSale.escrowPDA.toString());
+      });
     });
-  });
 
-  // helpers
-  const airdrop = async (publicKey: anchor.web3.PublicKey, amount: number) => {
-    const sig = await program.provider.connection.requestAirdrop(
-      publicKey,
-      amount
-    );
-    await program.provider.connection.confirmTransaction(sig, "confirmed");
-  };
+    describe("cancel escrow", () => {
+      const price = new BN(100_000_000); // 0.1 SOL
 
-  // get PDA for the protocol
-  const getProtocolAddress = (programID: PublicKey) => {
-    return PublicKey.findProgramAddressSync(
-      [anchor.utils.bytes.utf8.encode("protocol_state")],
-      programID
-    );
-  };
+      it("does not allow an agreement in escrow to be cancelled directly", async () => {
+        const sale = await setupEscrowSale("444111", "44411", price, false);
 
-  const getRegistrarPDA = (authority: PublicKey) => {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("registrar"), authority.toBuffer()],
-      program.programId
-    )[0];
-  };
+        try {
+          await program.methods
+            .cancelAgreement("Buyer is taking too long")
+            .accounts({
+              authority: sale.seller.publicKey,
+              titleDeed: sale.titleDeedPDA,
+              agreement: sale.agreementPDA,
+              agreementRecord: await getNextAgreementRecordPDA(sale.titleDeedPDA),
+              agreementIndex: sale.agreementIndexPDA,
+              escrow: sale.escrowPDA,
+              protocolState,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([sale.seller])
+            .rpc();
+          assert.fail("Expected transaction to fail");
+        } catch (error) {
+          assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+          const anchorError = error as anchor.AnchorError;
+          assert.equal(
+            anchorError.error?.errorCode?.code,
+            "AgreementInEscrow",
+            `Expected AgreementInEscrow error, got: ${anchorError.error?.errorCode?.code}`
+          );
+        }
+      });
 
-  const getUserAddress = (idNumber: string, authority: PublicKey) => {
-    const utf8Bytes = Buffer.from(idNumber, "utf-8");
-    const hash = crypto.createHash("sha256").update(utf8Bytes).digest();
-    const idNumberSeed = new Uint8Array(hash);
+      it("allows the seller to cancel an escrow before payment", async () => {
+        const sale = await setupEscrowSale("444222", "44422", price, false);
+        const agreementRecordPDA = await getNextAgreementRecordPDA(sale.titleDeedPDA);
+
+        await cancelEscrow(sale.seller, sale, null);
+
+        // the escrow is closed once it is unwound, the outcome is kept in the agreement record
+        const escrow = await program.provider.connection.getAccountInfo(sale.escrowPDA);
+        assert.equal(escrow, null, "Escrow should be closed");
+        const agreementRecord = await program.account.agreementRecord.fetch(agreementRecordPDA);
+        assert.ok("cancelled" in agreementRecord.outcome, "Record should be cancelled");
+        assert.equal(agreementRecord.reason, "Sale called off");
+        assert.equal(agreementRecord.closedBy.toString(), sale.seller.publicKey.toString());
+        assert.equal(agreementRecord.shareBps, 10000);
+
+        // title deed authority is returned to the seller
+        const titleDeed = await program.account.titleDeed.fetch(sale.titleDeedPDA);
+        assert.equal(titleDeed.authority.toString(), sale.seller.publicKey.toString());
+
+        // agreement accounts are closed
+        const agreement = await program.provider.connection.getAccountInfo(sale.agreementPDA);
+        assert.equal(agreement, null, "Agreement should be closed");
+        const agreementIndex = await program.provider.connection.getAccountInfo(
+          sale.agreementIndexPDA
+        );
+        assert.equal(agreementIndex, null, "Agreement index should be closed");
+      });
 
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("person"), idNumberSeed, authority.toBuffer()],
-      program.programId
-    )[0];
-  };
+      it("allows the same parties to agree again at the same price after cancelling", async () => {
+        const sale = await setupEscrowSale("444555", "44455", price, false);
+        await cancelEscrow(sale.seller, sale, null);
 
-  const getIdNumberClaimPDA = (idNumber: string) => {
-    const utf8Bytes = Buffer.from(idNumber, "utf-8");
-    const hash = crypto.createHash("sha256").update(utf8Bytes).digest();
-    const idNumberSeed = new Uint8Array(hash);
+        // the agreement and escrow addresses are the same as the cancelled ones
+        await makeAgreement(
+          sale.seller,
+          price,
+          sale.titleDeedPDA,
+          getTitleForSalePDA(sale.titleDeedPDA, sale.seller.publicKey),
+          sale.sellerPDA,
+          sale.buyerPDA,
+          getSearchCertificatePDA(sale.titleDeedPDA, sale.buyer.publicKey),
+          sale.agreementPDA,
+          sale.agreementIndexPDA
+        );
+        await signAgreement(sale.buyer, price, sale.titleDeedPDA, sale.agreementPDA);
+        await createEscrow(
+          sale.seller,
+          sale.titleDeedPDA,
+          sale.agreementPDA,
+          sale.sellerPDA,
+          sale.buyerPDA,
+          sale.escrowPDA
+        );
 
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("id_number_claim"), idNumberSeed],
-      program.programId
-    )[0];
-  };
+        const escrow = await program.account.escrow.fetch(sale.escrowPDA);
+        assert.ok("titleDeposited" in escrow.state, "Escrow should hold the title again");
+      });
 
-  const getTitleDeedPDA = (owner: PublicKey) => {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("title_deed"), owner.toBuffer()],
-      program.programId
-    )[0];
-  };
+      it("does not allow the seller to cancel an escrow once the buyer has paid", async () => {
+        const sale = await setupEscrowSale("444333", "44433", price, true);
 
-  const getTitleForSalePDA = (titleDeed: PublicKey, seller: PublicKey) => {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("title_for_sale"), seller.toBuffer(), titleDeed.toBuffer()],
-      program.programId
-    )[0];
-  };
+        try {
+          await cancelEscrow(sale.seller, sale, sale.depositPDA);
+          assert.fail("Expected transaction to fail");
+        } catch (error) {
+          assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+          const anchorError = error as anchor.AnchorError;
+          assert.equal(
+            anchorError.error?.errorCode?.code,
+            "Unauthorized",
+            `Expected Unauthorized error, got: ${anchorError.error?.errorCode?.code}`
+          );
+        }
+      });
 
-  const getTitleNumberLookupPDA = (titleNumber: string) => {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("title_number_lookup"), Buffer.from(titleNumber)],
-      program.programId
-    )[0];
-  };
+      it("refunds the buyer when they cancel an escrow after paying", async () => {
+        const sale = await setupEscrowSale("444444", "44444", price, true);
+        const buyerBalanceBefore = await program.provider.connection.getBalance(
+          sale.buyer.publicKey
+        );
 
-  const getAgreementPDA = (
-    seller: PublicKey,
-    buyer: PublicKey,
-    titleDeed: PublicKey,
-    price: BN
-  ) => {
-    // price.to_le_bytes() in Rust returns 8 bytes in little-endian format
-    const priceBuffer = Buffer.allocUnsafe(8);
-    price.toArrayLike(Buffer, "le", 8).copy(priceBuffer);
-    return PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("agreement"),
-        seller.toBuffer(),
-        buyer.toBuffer(),
-        titleDeed.toBuffer(),
-        priceBuffer,
-      ],
-      program.programId
-    )[0];
-  };
+        await cancelEscrow(sale.buyer, sale, sale.depositPDA);
 
-  const getAgreementIndexPDA = (titleDeed: PublicKey) => {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("agreement_index"), titleDeed.toBuffer()],
-      program.programId
-    )[0];
-  };
+        const buyerBalanceAfter = await program.provider.connection.getBalance(
+          sale.buyer.publicKey
+        );
+        assert.ok(
+          buyerBalanceAfter - buyerBalanceBefore >= price.toNumber(),
+          `Buyer should be refunded at least ${price.toString()} lamports`
+        );
 
-  const getEscrowPDA = (agreement: PublicKey) => {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("escrow"), agreement.toBuffer()],
-      program.programId
-    )[0];
-  };
+        const deposit = await program.provider.connection.getAccountInfo(sale.depositPDA);
+        assert.equal(deposit, null, "Deposit should be closed");
 
-  const getDepositPDA = (escrow: PublicKey) => {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("deposit"), escrow.toBuffer()],
-      program.programId
-    )[0];
-  };
+        const escrow = await program.provider.connection.getAccountInfo(sale.escrowPDA);
+        assert.equal(escrow, null, "Escrow should be closed");
+        const titleDeed = await program.account.titleDeed.fetch(sale.titleDeedPDA);
+        assert.equal(titleDeed.authority.toString(), sale.seller.publicKey.toString());
+      });
+    });
 
-  const getOwnershipHistoryPDA = (
-    titleDeed: PublicKey,
-    sequenceNumber: number
-  ) => {
-    const sequenceBuffer = Buffer.allocUnsafe(8);
-    sequenceBuffer.writeBigUInt64LE(BigInt(sequenceNumber), 0);
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("ownership_history"), titleDeed.toBuffer(), sequenceBuffer],
-      program.programId
-    )[0];
-  };
+    describe("expire escrow", () => {
+      it("does not allow an escrow to be expired before its deadline", async () => {
+        const sale = await setupEscrowSale("555111", "55511", new BN(100_000_000), true);
+        // anyone can crank an expired escrow
+        const cranker = anchor.web3.Keypair.generate();
+        await airdrop(cranker.publicKey, 100_000_000);
 
-  // Instruction helper functions
-  const createUserAccount = async (
-    authority: anchor.web3.Keypair,
-    firstName: string,
-    lastName: string,
-    idNumber: string,
-    phoneNumber: string,
-    userPDA: PublicKey,
-    idNumberClaimPDA: PublicKey
-  ) => {
-    await program.methods
-      .createUserAccount(firstName, lastName, idNumber, phoneNumber)
-      .accounts({
-        authority: authority.publicKey,
-        user: userPDA,
-        idNumberClaim: idNumberClaimPDA,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      })
-      .signers([authority])
-      .rpc();
-  };
+        try {
+          await program.methods
+            .expireEscrow()
+            .accounts({
+              authority: cranker.publicKey,
+              escrow: sale.escrowPDA,
+              titleDeed: sale.titleDeedPDA,
+              agreement: sale.agreementPDA,
+              agreementIndex: sale.agreementIndexPDA,
+              agreementRecord: await getNextAgreementRecordPDA(sale.titleDeedPDA),
+              deposit: sale.depositPDA,
+              sellerAuthority: sale.seller.publicKey,
+              buyerAuthority: sale.buyer.publicKey,
+              protocolState,
+              systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([cranker])
+            .rpc();
+          assert.fail("Expected transaction to fail");
+        } catch (error) {
+          assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+          const anchorError = error as anchor.AnchorError;
+          assert.equal(
+            anchorError.error?.errorCode?.code,
+            "EscrowDeadlineNotReached",
+            `Expected EscrowDeadlineNotReached error, got: ${anchorError.error?.errorCode?.code}`
+          );
+        }
 
-  const assignTitleDeedToOwner = async (
-    registrar: anchor.web3.Keypair,
-    registrarPDA: PublicKey,
-    newOwnerAddress: PublicKey,
-    titleNumber: string,
-    location: string,
-    acreage: number,
-    districtLandRegistry: string,
-    registryMapsheetNumber: BN,
-    titleDeedPDA: PublicKey,
-    ownerPDA: PublicKey
-  ) => {
-    const ownershipHistoryPDA = getOwnershipHistoryPDA(titleDeedPDA, 0);
-    await program.methods
-      .assignTitleDeedToOwner(
-        newOwnerAddress,
-        titleNumber,
-        location,
-        acreage,
-        districtLandRegistry,
-        registryMapsheetNumber
-      )
-      .accounts({
-        authority: registrar.publicKey,
-        registrar: registrarPDA,
-        titleDeed: titleDeedPDA,
-        owner: ownerPDA,
-        ownershipHistory: ownershipHistoryPDA,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      })
-      .signers([registrar])
-      .rpc();
-  };
+        const escrow = await program.account.escrow.fetch(sale.escrowPDA);
+        assert.ok("paymentDeposited" in escrow.state, "Escrow should still be open");
+      });
+    });
 
-  const markTitleForSale = async (
-    authority: anchor.web3.Keypair,
-    price: BN,
-    titleDeedPDA: PublicKey,
-    sellerPDA: PublicKey,
-    titleForSalePDA: PublicKey
-  ) => {
-    await program.methods
-      .markTitleForSale(price)
-      .accounts({
-        authority: authority.publicKey,
-        titleDeed: titleDeedPDA,
-        seller: sellerPDA,
-        titleForSale: titleForSalePDA,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      })
-      .signers([authority])
-      .rpc();
-  };
+    describe("reject escrow", () => {
+      const price = new BN(100_000_000); // 0.1 SOL
+
+      it("does not allow a registrar outside the title's district to reject an escrow", async () => {
+        const sale = await setupEscrowSale("666111", "66611", price, true);
+
+        try {
+          // registrar1 only covers Nairobi, the sale is in Nakuru
+          await rejectEscrow(registrar1, registrar1PDA, sale, { ownershipDispute: {} }, "");
+          assert.fail("Expected transaction to fail");
+        } catch (error) {
+          assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+          const anchorError = error as anchor.AnchorError;
+          assert.equal(
+            anchorError.error?.errorCode?.code,
+            "OutsideRegistrarJurisdiction",
+            `Expected OutsideRegistrarJurisdiction error, got: ${anchorError.error?.errorCode?.code}`
+          );
+        }
+      });
+
+      it("allows a registrar to reject an escrow, refunding the buyer and recording the reason", async () => {
+        const sale = await setupEscrowSale("666222", "66622", price, true);
+        const buyerBalanceBefore = await program.provider.connection.getBalance(
+          sale.buyer.publicKey
+        );
+        const agreementRecordPDA = await getNextAgreementRecordPDA(sale.titleDeedPDA);
+
+        await rejectEscrow(
+          registrar2,
+          registrar2PDA,
+          sale,
+          { ownershipDispute: {} },
+          "Title is subject to an ongoing court case"
+        );
+
+        const escrow = await program.provider.connection.getAccountInfo(sale.escrowPDA);
+        assert.equal(escrow, null, "Escrow should be closed");
+
+        // buyer is refunded and the title returned to the seller
+        const buyerBalanceAfter = await program.provider.connection.getBalance(
+          sale.buyer.publicKey
+        );
+        assert.ok(
+          buyerBalanceAfter - buyerBalanceBefore >= price.toNumber(),
+          `Buyer should be refunded at least ${price.toString()} lamports`
+        );
+        const titleDeed = await program.account.titleDeed.fetch(sale.titleDeedPDA);
+        assert.equal(titleDeed.authority.toString(), sale.seller.publicKey.toString());
+
+        const agreementRecord = await program.account.agreementRecord.fetch(
+          agreementRecordPDA
+        );
+        assert.ok("rejected" in agreementRecord.outcome, "Record should be rejected");
+        assert.ok(
+          "ownershipDispute" in agreementRecord.rejectionReason,
+          "Record should carry the rejection reason"
+        );
+        assert.equal(agreementRecord.reason, "Title is subject to an ongoing court case");
+        assert.equal(agreementRecord.closedBy.toString(), registrar2.publicKey.toString());
+      });
+    });
+  });
+
+  describe("gift transfers", () => {
+    it("does not allow a gift to be approved before the recipient accepts", async () => {
+      const {
+        owner: donor,
+        ownerPDA: donorPDA,
+        counterpartyPDA: recipientPDA,
+        titleDeedPDA,
+      } = await setupRegisteredTitle("777111", "77711");
+      await initiateGiftTransfer(donor, donorPDA, titleDeedPDA, recipientPDA);
+
+      try {
+        await approveGiftTransfer(registrar2, registrar2PDA, titleDeedPDA, donor.publicKey, donorPDA);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "GiftNotAccepted",
+          `Expected GiftNotAccepted error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("transfers a gifted title once the recipient accepts and a registrar approves", async () => {
+      const {
+        owner: donor,
+        ownerPDA: donorPDA,
+        counterparty: recipient,
+        counterpartyPDA: recipientPDA,
+        titleDeedPDA,
+      } = await setupRegisteredTitle("777222", "77722");
+      const giftTransferPDA = getGiftTransferPDA(titleDeedPDA);
+
+      await initiateGiftTransfer(donor, donorPDA, titleDeedPDA, recipientPDA);
+      await program.methods
+        .acceptGiftTransfer()
+        .accounts({
+          authority: recipient.publicKey,
+          titleDeed: titleDeedPDA,
+          giftTransfer: giftTransferPDA,
+          protocolState,
+        })
+        .signers([recipient])
+        .rpc();
+
+      const giftTransfer = await program.account.giftTransfer.fetch(giftTransferPDA);
+      assert.equal(giftTransfer.donor.toString(), donor.publicKey.toString());
+      assert.ok(giftTransfer.acceptedAt !== null, "Gift should be accepted");
+
+      await approveGiftTransfer(registrar2, registrar2PDA, titleDeedPDA, donor.publicKey, donorPDA);
+
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.owner.authority.toString(), recipient.publicKey.toString());
+      assert.equal(titleDeed.authority.toString(), recipient.publicKey.toString());
+      assert.equal(titleDeed.totalTransfers.toNumber(), 1);
+
+      const ownershipHistory = await program.account.ownershipHistory.fetch(
+        getOwnershipHistoryPDA(titleDeedPDA, 1)
+      );
+      assert.ok("gift" in ownershipHistory.transferType, "Transfer should be a gift");
+      assert.equal(ownershipHistory.previousOwner.toString(), donor.publicKey.toString());
+      assert.equal(ownershipHistory.currentOwner.toString(), recipient.publicKey.toString());
+
+      const giftTransferAfter = await program.provider.connection.getAccountInfo(giftTransferPDA);
+      assert.equal(giftTransferAfter, null, "Gift transfer should be closed");
+    });
+
+    it("allows a title to be gifted once its owner delists it", async () => {
+      const {
+        owner: donor,
+        ownerPDA: donorPDA,
+        counterparty: recipient,
+        counterpartyPDA: recipientPDA,
+        titleDeedPDA,
+      } = await setupRegisteredTitle("405555", "40555");
+      const titleForSalePDA = getTitleForSalePDA(titleDeedPDA, donor.publicKey);
+      await markTitleForSale(donor, new BN(100_000_000), titleDeedPDA, donorPDA, titleForSalePDA);
+
+      try {
+        await initiateGiftTransfer(donor, donorPDA, titleDeedPDA, recipientPDA);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "TitleIsForSale",
+          `Expected TitleIsForSale error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      await program.methods
+        .delistTitle()
+        .accounts({
+          authority: donor.publicKey,
+          titleDeed: titleDeedPDA,
+          titleForSale: titleForSalePDA,
+          agreementIndex: getAgreementIndexPDA(titleDeedPDA),
+          protocolState,
+        })
+        .signers([donor])
+        .rpc();
+
+      const delistedTitleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(delistedTitleDeed.isForSale, false);
+      const listing = await program.provider.connection.getAccountInfo(titleForSalePDA);
+      assert.equal(listing, null, "Listing should be closed");
+
+      await initiateGiftTransfer(donor, donorPDA, titleDeedPDA, recipientPDA);
+      await program.methods
+        .acceptGiftTransfer()
+        .accounts({
+          authority: recipient.publicKey,
+          titleDeed: titleDeedPDA,
+          giftTransfer: getGiftTransferPDA(titleDeedPDA),
+          protocolState,
+        })
+        .signers([recipient])
+        .rpc();
+      await approveGiftTransfer(registrar2, registrar2PDA, titleDeedPDA, donor.publicKey, donorPDA);
+
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.owner.authority.toString(), recipient.publicKey.toString());
+    });
+  });
+
+  describe("succession", () => {
+    it("blocks dealings on a title once its owner is recorded as deceased", async () => {
+      const { owner, ownerPDA, counterparty, titleDeedPDA } =
+        await setupRegisteredTitle("888111", "88811");
+      const administrator = anchor.web3.Keypair.generate();
+      await recordSuccession(titleDeedPDA, ownerPDA, "HCSC-E001-2025", administrator.publicKey, [
+        counterparty.publicKey,
+      ]);
+
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.inSuccession, true);
+
+      try {
+        await markTitleForSale(
+          owner,
+          new BN(100_000_000),
+          titleDeedPDA,
+          ownerPDA,
+          getTitleForSalePDA(titleDeedPDA, owner.publicKey)
+        );
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "TitleInSuccession",
+          `Expected TitleInSuccession error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("blocks a deceased owner's other titles whichever user account they deal through", async () => {
+      const { owner, ownerPDA, counterparty, titleDeedPDA } =
+        await setupRegisteredTitle("405666", "40566");
+      const otherTitleDeedPDA = await registerAdditionalTitle(owner, ownerPDA, "405667");
+      await recordSuccession(titleDeedPDA, ownerPDA, "HCSC-E005-2025", counterparty.publicKey, [
+        counterparty.publicKey,
+      ]);
+
+      const deathRecord = await program.account.deathRecord.fetch(
+        getDeathRecordPDA(owner.publicKey)
+      );
+      assert.equal(deathRecord.authority.toString(), owner.publicKey.toString());
+      assert.equal(deathRecord.recordedBy.toString(), registrar2.publicKey.toString());
+
+      // a second user account held by the same wallet is still caught
+      const secondOwnerPDA = getUserAddress("405669", owner.publicKey);
+      await createUserAccount(
+        owner,
+        "Second",
+        "Account",
+        "405669",
+        "700000009",
+        secondOwnerPDA,
+        getIdNumberClaimPDA("405669")
+      );
+      try {
+        await markTitleForSale(
+          owner,
+          new BN(100_000_000),
+          otherTitleDeedPDA,
+          secondOwnerPDA,
+          getTitleForSalePDA(otherTitleDeedPDA, owner.publicKey)
+        );
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "OwnerDeceased",
+          `Expected OwnerDeceased error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("transfers a deceased owner's title to a named beneficiary", async () => {
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("888222", "88822");
+      const administrator = anchor.web3.Keypair.generate();
+      const successionPDA = getSuccessionPDA(titleDeedPDA);
+      await recordSuccession(titleDeedPDA, ownerPDA, "HCSC-E002-2025", administrator.publicKey, [
+        counterparty.publicKey,
+      ]);
+
+      const succession = await program.account.succession.fetch(successionPDA);
+      assert.equal(succession.deceased.toString(), owner.publicKey.toString());
+      assert.equal(succession.administrator.toString(), administrator.publicKey.toString());
+      assert.equal(succession.courtGrantReference, "HCSC-E002-2025");
+
+      await program.methods
+        .transferToBeneficiary(10000)
+        .accounts({
+          authority: registrar2.publicKey,
+          registrar: registrar2PDA,
+          administrator: administrator.publicKey,
+          titleDeed: titleDeedPDA,
+          succession: successionPDA,
+          beneficiary: counterpartyPDA,
+          ownershipHistory: getOwnershipHistoryPDA(titleDeedPDA, 1),
+          protocolState,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([registrar2, administrator])
+        .rpc();
+
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.owner.authority.toString(), counterparty.publicKey.toString());
+      assert.equal(titleDeed.inSuccession, false);
+
+      const ownershipHistory = await program.account.ownershipHistory.fetch(
+        getOwnershipHistoryPDA(titleDeedPDA, 1)
+      );
+      assert.ok(
+        "inheritance" in ownershipHistory.transferType,
+        "Transfer should be an inheritance"
+      );
+      assert.equal(ownershipHistory.previousOwner.toString(), owner.publicKey.toString());
+
+      const successionAfter = await program.provider.connection.getAccountInfo(successionPDA);
+      assert.equal(successionAfter, null, "Succession should be closed");
+    });
+
+    it("freezes the deceased's other titles and splits the estate among the beneficiaries", async () => {
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("404444", "40444");
+      const otherTitleDeedPDA = await registerAdditionalTitle(owner, ownerPDA, "404445");
+      const beneficiary = anchor.web3.Keypair.generate();
+      await airdrop(beneficiary.publicKey, 100_000_000);
+      const beneficiaryPDA = getUserAddress("404446", beneficiary.publicKey);
+      await createUserAccount(
+        beneficiary,
+        "Second",
+        "Beneficiary",
+        "404446",
+        "700000404",
+        beneficiaryPDA,
+        getIdNumberClaimPDA("404446")
+      );
+      const administrator = anchor.web3.Keypair.generate();
+      const successionPDA = getSuccessionPDA(titleDeedPDA);
+      await recordSuccession(titleDeedPDA, ownerPDA, "HCSC-E404-2025", administrator.publicKey, [
+        counterparty.publicKey,
+        beneficiary.publicKey,
+      ]);
+
+      const deceased = await program.account.user.fetch(ownerPDA);
+      assert.ok(deceased.deceasedAt !== null, "Owner should be recorded as deceased");
+
+      // the deceased's other title cannot be sold off either
+      try {
+        await markTitleForSale(
+          owner,
+          new BN(100_000_000),
+          otherTitleDeedPDA,
+          ownerPDA,
+          getTitleForSalePDA(otherTitleDeedPDA, owner.publicKey)
+        );
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "OwnerDeceased",
+          `Expected OwnerDeceased error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      // the title stays in succession until the whole estate has passed on
+      await transferToBeneficiary(administrator, titleDeedPDA, counterpartyPDA, 6000);
+      let titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.inSuccession, true);
+      assert.ok(
+        (await program.provider.connection.getAccountInfo(successionPDA)) !== null,
+        "Succession should stay open"
+      );
+
+      await transferToBeneficiary(administrator, titleDeedPDA, beneficiaryPDA, 4000);
+      titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.inSuccession, false);
+      assert.ok("tenancyInCommon" in titleDeed.tenure, "Beneficiaries should hold in common");
+      assert.deepEqual(
+        titleDeed.coOwners.map(({ authority, shareBps }) => [authority.toString(), shareBps]),
+        [
+          [counterparty.publicKey.toString(), 6000],
+          [beneficiary.publicKey.toString(), 4000],
+        ]
+      );
+
+      const ownershipHistory = await program.account.ownershipHistory.fetch(
+        getOwnershipHistoryPDA(titleDeedPDA, 2)
+      );
+      assert.equal(ownershipHistory.currentOwner.toString(), beneficiary.publicKey.toString());
+      assert.equal(ownershipHistory.shareBps, 4000);
+
+      const successionAfter = await program.provider.connection.getAccountInfo(successionPDA);
+      assert.equal(successionAfter, null, "Succession should be closed");
+    });
+
+    it("passes a deceased tenant in common's share to their beneficiary", async () => {
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("404555", "40455");
+      await registerCoOwnership(owner, titleDeedPDA, { tenancyInCommon: {} }, [
+        { authority: owner.publicKey, shareBps: 6000 },
+        { authority: counterparty.publicKey, shareBps: 4000 },
+      ]);
+      const administrator = anchor.web3.Keypair.generate();
+      await recordSuccession(
+        titleDeedPDA,
+        counterpartyPDA,
+        "HCSC-E405-2025",
+        administrator.publicKey,
+        [owner.publicKey]
+      );
+
+      const succession = await program.account.succession.fetch(getSuccessionPDA(titleDeedPDA));
+      assert.equal(succession.deceased.toString(), counterparty.publicKey.toString());
+
+      await transferToBeneficiary(administrator, titleDeedPDA, ownerPDA, 4000);
+
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.inSuccession, false);
+      assert.ok("sole" in titleDeed.tenure, "Beneficiary should hold the whole title");
+      assert.equal(titleDeed.owner.authority.toString(), owner.publicKey.toString());
+      assert.deepEqual(
+        titleDeed.coOwners.map(({ authority, shareBps }) => [authority.toString(), shareBps]),
+        [[owner.publicKey.toString(), 10000]]
+      );
+    });
+
+    it("passes a deceased joint tenant's share to the survivor", async () => {
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("404666", "40466");
+      await registerCoOwnership(owner, titleDeedPDA, { jointTenancy: {} }, [
+        { authority: owner.publicKey, shareBps: 5000 },
+        { authority: counterparty.publicKey, shareBps: 5000 },
+      ]);
+
+      // joint tenants' shares do not pass through succession
+      try {
+        await recordSuccession(titleDeedPDA, ownerPDA, "HCSC-E406-2025", owner.publicKey, [
+          counterparty.publicKey,
+        ]);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "TitleIsJointTenancy",
+          `Expected TitleIsJointTenancy error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      await program.methods
+        .recordSurvivorship()
+        .accounts({
+          authority: registrar2.publicKey,
+          registrar: registrar2PDA,
+          titleDeed: titleDeedPDA,
+          deceased: ownerPDA,
+          deathRecord: getDeathRecordPDA(owner.publicKey),
+          survivor: counterpartyPDA,
+          ownershipHistory: getOwnershipHistoryPDA(titleDeedPDA, 1),
+          protocolState,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([registrar2])
+        .rpc();
+
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.ok("sole" in titleDeed.tenure, "Survivor should hold the whole title");
+      assert.equal(titleDeed.owner.authority.toString(), counterparty.publicKey.toString());
+      assert.equal(titleDeed.authority.toString(), counterparty.publicKey.toString());
+      assert.deepEqual(
+        titleDeed.coOwners.map(({ authority, shareBps }) => [authority.toString(), shareBps]),
+        [[counterparty.publicKey.toString(), 10000]]
+      );
+
+      const ownershipHistory = await program.account.ownershipHistory.fetch(
+        getOwnershipHistoryPDA(titleDeedPDA, 1)
+      );
+      assert.ok("survivorship" in ownershipHistory.transferType, "Transfer should be by survivorship");
+      assert.equal(ownershipHistory.previousOwner.toString(), owner.publicKey.toString());
+      assert.equal(ownershipHistory.shareBps, 5000);
+
+      const deceased = await program.account.user.fetch(ownerPDA);
+      assert.ok(deceased.deceasedAt !== null, "Joint tenant should be recorded as deceased");
+    });
+  });
+
+  describe("co-ownership", () => {
+    it("requires every co-owner to sign a sale listing", async () => {
+      const { owner, ownerPDA, counterparty, titleDeedPDA } =
+        await setupRegisteredTitle("999111", "99911");
+      await registerCoOwnership(owner, titleDeedPDA, { tenancyInCommon: {} }, [
+        { authority: owner.publicKey, shareBps: 6000 },
+        { authority: counterparty.publicKey, shareBps: 4000 },
+      ]);
+
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.ok("tenancyInCommon" in titleDeed.tenure, "Title should be held in common");
+      assert.equal(titleDeed.coOwners.length, 2);
+
+      const titleForSalePDA = getTitleForSalePDA(titleDeedPDA, owner.publicKey);
+      try {
+        await markTitleForSale(owner, new BN(100_000_000), titleDeedPDA, ownerPDA, titleForSalePDA);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "MissingCoOwnerSignature",
+          `Expected MissingCoOwnerSignature error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      await markTitleForSale(owner, new BN(100_000_000), titleDeedPDA, ownerPDA, titleForSalePDA, [
+        counterparty,
+      ]);
+      const titleDeedAfter = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeedAfter.isForSale, true);
+    });
+
+    it("does not allow joint tenants to hold unequal shares", async () => {
+      const { owner, counterparty, titleDeedPDA } = await setupRegisteredTitle("999222", "99922");
+
+      try {
+        await registerCoOwnership(owner, titleDeedPDA, { jointTenancy: {} }, [
+          { authority: owner.publicKey, shareBps: 7000 },
+          { authority: counterparty.publicKey, shareBps: 3000 },
+        ]);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "InvalidCoOwnerShares",
+          `Expected InvalidCoOwnerShares error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("splits sale proceeds pro rata between co-owners", async () => {
+      const price = new BN(100_000_000); // 0.1 SOL
+      const { owner, ownerPDA, counterparty, titleDeedPDA } =
+        await setupRegisteredTitle("999333", "99933");
+      await registerCoOwnership(owner, titleDeedPDA, { tenancyInCommon: {} }, [
+        { authority: owner.publicKey, shareBps: 7500 },
+        { authority: counterparty.publicKey, shareBps: 2500 },
+      ]);
+
+      const buyer = anchor.web3.Keypair.generate();
+      await airdrop(buyer.publicKey, price.toNumber() + 100_000_000);
+      const buyerPDA = getUserAddress("999334", buyer.publicKey);
+      await createUserAccount(
+        buyer,
+        "Co-owned",
+        "Buyer",
+        "999334",
+        "700000005",
+        buyerPDA,
+        getIdNumberClaimPDA("999334")
+      );
+
+      const titleForSalePDA = getTitleForSalePDA(titleDeedPDA, owner.publicKey);
+      const agreementPDA = getAgreementPDA(owner.publicKey, buyer.publicKey, titleDeedPDA, price);
+      const agreementIndexPDA = getAgreementIndexPDA(titleDeedPDA);
+      const escrowPDA = getEscrowPDA(agreementPDA);
+      const depositPDA = getDepositPDA(escrowPDA);
+      const searchCertificatePDA = getSearchCertificatePDA(titleDeedPDA, buyer.publicKey);
+
+      await markTitleForSale(owner, price, titleDeedPDA, ownerPDA, titleForSalePDA, [counterparty]);
+      await searchTitleDeedByNumber(
+        buyer,
+        "999333",
+        getTitleNumberLookupPDA("999333"),
+        titleDeedPDA,
+        buyerPDA
+      );
+      await makeAgreement(
+        owner,
+        price,
+        titleDeedPDA,
+        titleForSalePDA,
+        ownerPDA,
+        buyerPDA,
+        searchCertificatePDA,
+        agreementPDA,
+        agreementIndexPDA,
+        [counterparty]
+      );
+      await signAgreement(buyer, price, titleDeedPDA, agreementPDA);
+      await createEscrow(owner, titleDeedPDA, agreementPDA, ownerPDA, buyerPDA, escrowPDA, [
+        counterparty,
+      ]);
+      await depositPaymentToEscrow(
+        buyer,
+        price,
+        buyerPDA,
+        ownerPDA,
+        escrowPDA,
+        agreementPDA,
+        depositPDA
+      );
+
+      const counterpartyBalanceBefore = await program.provider.connection.getBalance(
+        counterparty.publicKey
+      );
+      await program.methods
+        .authorizeEscrow()
+        .accounts({
+          authority: registrar2.publicKey,
+          registrar: registrar2PDA,
+          escrow: escrowPDA,
+          deposit: depositPDA,
+          titleDeed: titleDeedPDA,
+          ownershipHistory: getOwnershipHistoryPDA(titleDeedPDA, 1),
+          titleForSale: titleForSalePDA,
+          agreement: agreementPDA,
+          agreementIndex: agreementIndexPDA,
+          agreementRecord: await getNextAgreementRecordPDA(titleDeedPDA),
+          searchCertificate: searchCertificatePDA,
+          buyer: buyerPDA,
+          seller: ownerPDA,
+          sellerAuthority: owner.publicKey,
+          deathRecord: getDeathRecordPDA(owner.publicKey),
+          buyerAuthority: buyer.publicKey,
+          protocolState,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: counterparty.publicKey, isSigner: false, isWritable: true },
+        ])
+        .signers([registrar2])
+        .rpc();
+
+      // the co-owner receives their 25% share
+      const counterpartyBalanceAfter = await program.provider.connection.getBalance(
+        counterparty.publicKey
+      );
+      assert.equal(counterpartyBalanceAfter - counterpartyBalanceBefore, 25_000_000);
+
+      // the buyer becomes the sole owner
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.ok("sole" in titleDeed.tenure, "Buyer should own the title outright");
+      assert.equal(titleDeed.coOwners.length, 1);
+      assert.equal(titleDeed.coOwners[0].authority.toString(), buyer.publicKey.toString());
+    });
+  });
+
+  describe("share transfers", () => {
+    it("does not allow a co-owner to list more than their share", async () => {
+      const { owner, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("101111", "10111");
+      await registerCoOwnership(owner, titleDeedPDA, { tenancyInCommon: {} }, [
+        { authority: owner.publicKey, shareBps: 6000 },
+        { authority: counterparty.publicKey, shareBps: 4000 },
+      ]);
+
+      try {
+        await markShareForSale(counterparty, 5000, new BN(50_000_000), titleDeedPDA, counterpartyPDA);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "InvalidShare",
+          `Expected InvalidShare error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("allows a co-owner to unlist their share so the whole title can be listed", async () => {
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("405444", "40544");
+      await registerCoOwnership(owner, titleDeedPDA, { tenancyInCommon: {} }, [
+        { authority: owner.publicKey, shareBps: 6000 },
+        { authority: counterparty.publicKey, shareBps: 4000 },
+      ]);
+      await markShareForSale(counterparty, 2000, new BN(50_000_000), titleDeedPDA, counterpartyPDA);
+
+      // the whole title cannot be listed while a share of it is on the market
+      const titleForSalePDA = getTitleForSalePDA(titleDeedPDA, owner.publicKey);
+      try {
+        await markTitleForSale(owner, new BN(100_000_000), titleDeedPDA, ownerPDA, titleForSalePDA, [
+          counterparty,
+        ]);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "TitleIsForSale",
+          `Expected TitleIsForSale error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      await unlistShare(counterparty, titleDeedPDA);
+      const shareListing = await program.provider.connection.getAccountInfo(
+        getTitleForSalePDA(titleDeedPDA, counterparty.publicKey)
+      );
+      assert.equal(shareListing, null, "Share listing should be closed");
+      const unlistedTitleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(unlistedTitleDeed.listedShares, 0);
+
+      await markTitleForSale(owner, new BN(100_000_000), titleDeedPDA, ownerPDA, titleForSalePDA, [
+        counterparty,
+      ]);
+      const listedTitleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(listedTitleDeed.isForSale, true);
+    });
+
+    it("allows a co-owner to sell part of their share through escrow", async () => {
+      const price = new BN(50_000_000); // 0.05 SOL
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("101222", "10122");
+      await registerCoOwnership(owner, titleDeedPDA, { tenancyInCommon: {} }, [
+        { authority: owner.publicKey, shareBps: 6000 },
+        { authority: counterparty.publicKey, shareBps: 4000 },
+      ]);
+
+      const buyer = anchor.web3.Keypair.generate();
+      await airdrop(buyer.publicKey, price.toNumber() + 100_000_000);
+      const buyerPDA = getUserAddress("101223", buyer.publicKey);
+      await createUserAccount(
+        buyer,
+        "Share",
+        "Buyer",
+        "101223",
+        "700000006",
+        buyerPDA,
+        getIdNumberClaimPDA("101223")
+      );
+
+      // the co-owner sells half of their share without the registered owner
+      const titleForSalePDA = getTitleForSalePDA(titleDeedPDA, counterparty.publicKey);
+      const agreementPDA = getAgreementPDA(
+        counterparty.publicKey,
+        buyer.publicKey,
+        titleDeedPDA,
+        price
+      );
+      const agreementIndexPDA = getAgreementIndexPDA(titleDeedPDA);
+      const escrowPDA = getEscrowPDA(agreementPDA);
+      const depositPDA = getDepositPDA(escrowPDA);
+      const searchCertificatePDA = getSearchCertificatePDA(titleDeedPDA, buyer.publicKey);
+
+      await markShareForSale(counterparty, 2000, price, titleDeedPDA, counterpartyPDA);
+      const listedTitleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(listedTitleDeed.listedShares, 1);
+
+      // the whole title cannot be given away while a share of it is listed
+      try {
+        await initiateGiftTransfer(owner, ownerPDA, titleDeedPDA, buyerPDA);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "TitleIsForSale",
+          `Expected TitleIsForSale error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      await searchTitleDeedByNumber(
+        buyer,
+        "101222",
+        getTitleNumberLookupPDA("101222"),
+        titleDeedPDA,
+        buyerPDA
+      );
+      await makeAgreement(
+        counterparty,
+        price,
+        titleDeedPDA,
+        titleForSalePDA,
+        counterpartyPDA,
+        buyerPDA,
+        searchCertificatePDA,
+        agreementPDA,
+        agreementIndexPDA
+      );
+      const agreement = await program.account.agreement.fetch(agreementPDA);
+      assert.equal(agreement.shareBps, 2000);
+
+      await signAgreement(buyer, price, titleDeedPDA, agreementPDA);
+      await createEscrow(
+        counterparty,
+        titleDeedPDA,
+        agreementPDA,
+        counterpartyPDA,
+        buyerPDA,
+        escrowPDA
+      );
+      await depositPaymentToEscrow(
+        buyer,
+        price,
+        buyerPDA,
+        counterpartyPDA,
+        escrowPDA,
+        agreementPDA,
+        depositPDA
+      );
+
+      const ownershipHistoryPDA = getOwnershipHistoryPDA(titleDeedPDA, 1);
+      const agreementRecordPDA = await getNextAgreementRecordPDA(titleDeedPDA);
+      await program.methods
+        .authorizeEscrow()
+        .accounts({
+          authority: registrar2.publicKey,
+          registrar: registrar2PDA,
+          escrow: escrowPDA,
+          deposit: depositPDA,
+          titleDeed: titleDeedPDA,
+          ownershipHistory: ownershipHistoryPDA,
+          titleForSale: titleForSalePDA,
+          agreement: agreementPDA,
+          agreementIndex: agreementIndexPDA,
+          agreementRecord: agreementRecordPDA,
+          searchCertificate: searchCertificatePDA,
+          buyer: buyerPDA,
+          seller: counterpartyPDA,
+          sellerAuthority: counterparty.publicKey,
+          deathRecord: getDeathRecordPDA(counterparty.publicKey),
+          buyerAuthority: buyer.publicKey,
+          protocolState,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([registrar2])
+        .rpc();
+
+      // the registered owner keeps the title, the buyer joins as a co-owner
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.listedShares, 0);
+      assert.equal(titleDeed.owner.authority.toString(), owner.publicKey.toString());
+      assert.equal(titleDeed.authority.toString(), owner.publicKey.toString());
+      const shares = Object.fromEntries(
+        titleDeed.coOwners.map((coOwner) => [coOwner.authority.toString(), coOwner.shareBps])
+      );
+      assert.equal(shares[owner.publicKey.toString()], 6000);
+      assert.equal(shares[counterparty.publicKey.toString()], 2000);
+      assert.equal(shares[buyer.publicKey.toString()], 2000);
+
+      const ownershipHistory = await program.account.ownershipHistory.fetch(ownershipHistoryPDA);
+      assert.equal(ownershipHistory.shareBps, 2000);
+      assert.equal(ownershipHistory.previousOwner.toString(), counterparty.publicKey.toString());
+      assert.equal(ownershipHistory.coOwners.length, 3);
+
+      // the record tells the share sale apart from a sale of the whole title
+      const agreementRecord = await program.account.agreementRecord.fetch(agreementRecordPDA);
+      assert.ok("completed" in agreementRecord.outcome, "Record should be completed");
+      assert.equal(agreementRecord.shareBps, 2000);
+    });
+
+    it("allows a sole owner to gift part of their title", async () => {
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("101333", "10133");
+      await initiateGiftTransfer(owner, ownerPDA, titleDeedPDA, counterpartyPDA, 2500);
+      await program.methods
+        .acceptGiftTransfer()
+        .accounts({
+          authority: counterparty.publicKey,
+          titleDeed: titleDeedPDA,
+          giftTransfer: getGiftTransferPDA(titleDeedPDA),
+          protocolState,
+        })
+        .signers([counterparty])
+        .rpc();
+      await approveGiftTransfer(registrar2, registrar2PDA, titleDeedPDA, owner.publicKey, ownerPDA);
+
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.ok("tenancyInCommon" in titleDeed.tenure, "Title should now be held in common");
+      assert.equal(titleDeed.owner.authority.toString(), owner.publicKey.toString());
+      assert.deepEqual(
+        titleDeed.coOwners.map((coOwner) => [coOwner.authority.toString(), coOwner.shareBps]),
+        [
+          [owner.publicKey.toString(), 7500],
+          [counterparty.publicKey.toString(), 2500],
+        ]
+      );
+    });
+  });
+
+  describe("subdivision", () => {
+    it("does not subdivide a title into parcels that do not add up to its acreage", async () => {
+      const { owner, titleDeedPDA } = await setupRegisteredTitle("202111", "20211");
+
+      try {
+        await subdivideTitleDeed(owner, titleDeedPDA, [
+          { titleNumber: "202111-1", acreage: 4 },
+          { titleNumber: "202111-2", acreage: 4 },
+        ]);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "InvalidParcelAcreage",
+          `Expected InvalidParcelAcreage error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("does not subdivide a co-owned title without every co-owner's consent", async () => {
+      const { owner, counterparty, titleDeedPDA } = await setupRegisteredTitle("202123", "20212");
+      await registerCoOwnership(owner, titleDeedPDA, { tenancyInCommon: {} }, [
+        { authority: owner.publicKey, shareBps: 5000 },
+        { authority: counterparty.publicKey, shareBps: 5000 },
+      ]);
+
+      try {
+        await subdivideTitleDeed(owner, titleDeedPDA, [
+          { titleNumber: "202123-1", acreage: 5 },
+          { titleNumber: "202123-2", acreage: 5 },
+        ]);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "MissingCoOwnerSignature",
+          `Expected MissingCoOwnerSignature error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("retires the parent title and registers its parcels to the same owner", async () => {
+      const { owner, ownerPDA, titleDeedPDA } = await setupRegisteredTitle("202222", "20222");
+      const parcels = [
+        { titleNumber: "202222-1", acreage: 2.5 },
+        { titleNumber: "202222-2", acreage: 7.5 },
+      ];
+      await subdivideTitleDeed(owner, titleDeedPDA, parcels);
+
+      const parent = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.ok(parent.retiredAt !== null, "Parent title should be retired");
+      assert.deepEqual(
+        parent.childTitles.map((child) => child.toString()),
+        parcels.map(({ titleNumber }) => getTitleDeedPDA(titleNumber).toString())
+      );
+
+      for (const { titleNumber, acreage } of parcels) {
+        const childPDA = getTitleDeedPDA(titleNumber);
+        const child = await program.account.titleDeed.fetch(childPDA);
+        assert.equal(child.titleNumber, titleNumber);
+        assert.equal(child.acreage, acreage);
+        assert.equal(child.owner.authority.toString(), owner.publicKey.toString());
+        assert.equal(child.authority.toString(), owner.publicKey.toString());
+        assert.deepEqual(child.parentTitles.map((p) => p.toString()), [titleDeedPDA.toString()]);
+
+        const lookup = await program.account.titleNumberLookup.fetch(getTitleNumberLookupPDA(titleNumber));
+        assert.equal(lookup.titleDeed.toString(), childPDA.toString());
+
+        const ownershipHistory = await program.account.ownershipHistory.fetch(
+          getOwnershipHistoryPDA(childPDA, 0)
+        );
+        assert.ok("subdivision" in ownershipHistory.transferType, "Transfer should be a subdivision");
+        assert.deepEqual(
+          ownershipHistory.parentTitles.map((p) => p.toString()),
+          [titleDeedPDA.toString()]
+        );
+      }
+
+      // the retired title can no longer be dealt with
+      try {
+        await markTitleForSale(
+          owner,
+          new BN(100_000_000),
+          titleDeedPDA,
+          ownerPDA,
+          getTitleForSalePDA(titleDeedPDA, owner.publicKey)
+        );
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "Unauthorized",
+          `Expected Unauthorized error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+  });
+
+  describe("amalgamation", () => {
+    it("does not amalgamate a title that is listed for sale", async () => {
+      const { owner, ownerPDA, titleDeedPDA } = await setupRegisteredTitle("202333", "20233");
+      const otherTitleDeedPDA = await registerAdditionalTitle(owner, ownerPDA, "202334");
+      await markTitleForSale(
+        owner,
+        new BN(100_000_000),
+        otherTitleDeedPDA,
+        ownerPDA,
+        getTitleForSalePDA(otherTitleDeedPDA, owner.publicKey)
+      );
+
+      try {
+        await amalgamateTitleDeeds(owner, "202335", [titleDeedPDA, otherTitleDeedPDA]);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "TitleIsForSale",
+          `Expected TitleIsForSale error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("does not amalgamate co-owned titles without every co-owner's consent", async () => {
+      const { owner, ownerPDA, counterparty, titleDeedPDA } =
+        await setupRegisteredTitle("202356", "20235");
+      const otherTitleDeedPDA = await registerAdditionalTitle(owner, ownerPDA, "202357");
+      for (const pda of [titleDeedPDA, otherTitleDeedPDA]) {
+        await registerCoOwnership(owner, pda, { tenancyInCommon: {} }, [
+          { authority: owner.publicKey, shareBps: 5000 },
+          { authority: counterparty.publicKey, shareBps: 5000 },
+        ]);
+      }
+
+      try {
+        await amalgamateTitleDeeds(owner, "202358", [titleDeedPDA, otherTitleDeedPDA]);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "MissingCoOwnerSignature",
+          `Expected MissingCoOwnerSignature error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      await amalgamateTitleDeeds(owner, "202358", [titleDeedPDA, otherTitleDeedPDA], [counterparty]);
+      const amalgamated = await program.account.titleDeed.fetch(getTitleDeedPDA("202358"));
+      assert.equal(amalgamated.coOwners.length, 2);
+    });
+
+    it("retires the source titles and registers one title with their combined acreage", async () => {
+      const { owner, ownerPDA, titleDeedPDA } = await setupRegisteredTitle("202444", "20244");
+      const otherTitleDeedPDA = await registerAdditionalTitle(owner, ownerPDA, "202445");
+      await amalgamateTitleDeeds(owner, "202446", [titleDeedPDA, otherTitleDeedPDA]);
+
+      const amalgamatedPDA = getTitleDeedPDA("202446");
+      const amalgamated = await program.account.titleDeed.fetch(amalgamatedPDA);
+      assert.equal(amalgamated.acreage, 20);
+      assert.equal(amalgamated.owner.authority.toString(), owner.publicKey.toString());
+      assert.deepEqual(
+        amalgamated.parentTitles.map((p) => p.toString()),
+        [titleDeedPDA.toString(), otherTitleDeedPDA.toString()]
+      );
+
+      const ownershipHistory = await program.account.ownershipHistory.fetch(
+        getOwnershipHistoryPDA(amalgamatedPDA, 0)
+      );
+      assert.ok("amalgamation" in ownershipHistory.transferType, "Transfer should be an amalgamation");
+      assert.equal(ownershipHistory.parentTitles.length, 2);
+
+      for (const sourcePDA of [titleDeedPDA, otherTitleDeedPDA]) {
+        const source = await program.account.titleDeed.fetch(sourcePDA);
+        assert.ok(source.retiredAt !== null, "Source title should be retired");
+        assert.deepEqual(source.childTitles.map((c) => c.toString()), [amalgamatedPDA.toString()]);
+      }
+    });
+  });
+
+  describe("charges", () => {
+    it("only lists a charged title at a price that pays off the lender", async () => {
+      const { owner, ownerPDA, titleDeedPDA } = await setupRegisteredTitle("202555", "20255");
+      const lender = anchor.web3.Keypair.generate();
+      const chargePDA = await registerCharge(owner, lender, titleDeedPDA, new BN(50_000_000));
+
+      const charge = await program.account.charge.fetch(chargePDA);
+      assert.equal(charge.lender.toString(), lender.publicKey.toString());
+      assert.equal(charge.priority.toNumber(), 1);
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.chargedAmount.toNumber(), 50_000_000);
+
+      const titleForSalePDA = getTitleForSalePDA(titleDeedPDA, owner.publicKey);
+      try {
+        await markTitleForSale(owner, new BN(40_000_000), titleDeedPDA, ownerPDA, titleForSalePDA);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "TitleIsCharged",
+          `Expected TitleIsCharged error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      await markTitleForSale(owner, new BN(100_000_000), titleDeedPDA, ownerPDA, titleForSalePDA);
+      const titleDeedAfter = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeedAfter.isForSale, true);
+    });
+
+    it("does not register a charge on a co-owned title without every co-owner's consent", async () => {
+      const { owner, counterparty, titleDeedPDA } = await setupRegisteredTitle("202556", "20256");
+      await registerCoOwnership(owner, titleDeedPDA, { tenancyInCommon: {} }, [
+        { authority: owner.publicKey, shareBps: 5000 },
+        { authority: counterparty.publicKey, shareBps: 5000 },
+      ]);
+      const lender = anchor.web3.Keypair.generate();
+
+      try {
+        await registerCharge(owner, lender, titleDeedPDA, new BN(50_000_000));
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "MissingCoOwnerSignature",
+          `Expected MissingCoOwnerSignature error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      await registerCharge(owner, lender, titleDeedPDA, new BN(50_000_000), [counterparty]);
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.chargedAmount.toNumber(), 50_000_000);
+    });
+
+    it("reports charges in searches and rejects a search made before a new charge", async () => {
+      const price = new BN(100_000_000);
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("202557", "20257");
+      const lender = anchor.web3.Keypair.generate();
+      await registerCharge(owner, lender, titleDeedPDA, new BN(30_000_000));
+
+      const titleForSalePDA = getTitleForSalePDA(titleDeedPDA, owner.publicKey);
+      await markTitleForSale(owner, price, titleDeedPDA, ownerPDA, titleForSalePDA);
+      await searchTitleDeedByNumber(
+        counterparty,
+        "202557",
+        getTitleNumberLookupPDA("202557"),
+        titleDeedPDA,
+        counterpartyPDA
+      );
+      const searchCertificatePDA = getSearchCertificatePDA(titleDeedPDA, counterparty.publicKey);
+      const searchCertificate = await program.account.searchCertificate.fetch(searchCertificatePDA);
+      assert.equal(searchCertificate.chargedAmount.toNumber(), 30_000_000);
+      assert.equal(searchCertificate.totalCharges.toNumber(), 1);
+
+      // a second lender charges the title after the buyer searched it
+      await registerCharge(owner, anchor.web3.Keypair.generate(), titleDeedPDA, new BN(20_000_000));
+      try {
+        await makeAgreement(
+          owner,
+          price,
+          titleDeedPDA,
+          titleForSalePDA,
+          ownerPDA,
+          counterpartyPDA,
+          searchCertificatePDA,
+          getAgreementPDA(owner.publicKey, counterparty.publicKey, titleDeedPDA, price),
+          getAgreementIndexPDA(titleDeedPDA)
+        );
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "SearchCertificateStale",
+          `Expected SearchCertificateStale error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("clears the outstanding amount once the lender discharges the charge", async () => {
+      const { owner, titleDeedPDA } = await setupRegisteredTitle("202666", "20266");
+      const lender = anchor.web3.Keypair.generate();
+      const chargePDA = await registerCharge(owner, lender, titleDeedPDA, new BN(50_000_000));
+
+      await program.methods
+        .dischargeCharge()
+        .accounts({
+          authority: registrar2.publicKey,
+          registrar: registrar2PDA,
+          lender: lender.publicKey,
+          titleDeed: titleDeedPDA,
+          charge: chargePDA,
+          protocolState,
+        })
+        .signers([registrar2, lender])
+        .rpc();
+
+      const charge = await program.account.charge.fetch(chargePDA);
+      assert.ok(charge.dischargedAt !== null, "Charge should be discharged");
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.chargedAmount.toNumber(), 0);
+      assert.equal(titleDeed.totalCharges.toNumber(), 1);
+    });
+
+    it("pays off the lender from the sale proceeds when a charged title is sold", async () => {
+      const price = new BN(100_000_000); // 0.1 SOL
+      const chargeAmount = new BN(30_000_000);
+      const lender = anchor.web3.Keypair.generate();
+      await airdrop(lender.publicKey, 10_000_000);
+      const chargePDA = getChargePDA(getTitleDeedPDA("202777"), 1);
+      const sale = await setupEscrowSale(
+        "202777",
+        "20277",
+        price,
+        true,
+        async (titleDeedPDA, seller) => {
+          await registerCharge(seller, lender, titleDeedPDA, chargeAmount);
+        }
+      );
+
+      const lenderBalanceBefore = await program.provider.connection.getBalance(lender.publicKey);
+      const sellerBalanceBefore = await program.provider.connection.getBalance(sale.seller.publicKey);
+      await program.methods
+        .authorizeEscrow()
+        .accounts({
+          authority: registrar2.publicKey,
+          registrar: registrar2PDA,
+          escrow: sale.escrowPDA,
+          deposit: sale.depositPDA,
+          titleDeed: sale.titleDeedPDA,
+          ownershipHistory: getOwnershipHistoryPDA(sale.titleDeedPDA, 1),
+          titleForSale: getTitleForSalePDA(sale.titleDeedPDA, sale.seller.publicKey),
+          agreement: sale.agreementPDA,
+          agreementIndex: sale.agreementIndexPDA,
+          agreementRecord: await getNextAgreementRecordPDA(sale.titleDeedPDA),
+          searchCertificate: getSearchCertificatePDA(sale.titleDeedPDA, sale.buyer.publicKey),
+          buyer: sale.buyerPDA,
+          seller: sale.sellerPDA,
+          sellerAuthority: sale.seller.publicKey,
+          deathRecord: getDeathRecordPDA(sale.seller.publicKey),
+          buyerAuthority: sale.buyer.publicKey,
+          protocolState,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: chargePDA, isSigner: false, isWritable: true },
+          { pubkey: lender.publicKey, isSigner: false, isWritable: true },
+        ])
+        .signers([registrar2])
+        .rpc();
+
+      const lenderBalanceAfter = await program.provider.connection.getBalance(lender.publicKey);
+      const sellerBalanceAfter = await program.provider.connection.getBalance(sale.seller.publicKey);
+      assert.equal(lenderBalanceAfter - lenderBalanceBefore, chargeAmount.toNumber());
+      assert.ok(
+        sellerBalanceAfter - sellerBalanceBefore >= price.sub(chargeAmount).toNumber(),
+        "Seller should receive the remainder of the price"
+      );
+
+      const charge = await program.account.charge.fetch(chargePDA);
+      assert.ok(charge.dischargedAt !== null, "Charge should be discharged");
+      const titleDeed = await program.account.titleDeed.fetch(sale.titleDeedPDA);
+      assert.equal(titleDeed.chargedAmount.toNumber(), 0);
+      assert.equal(titleDeed.owner.authority.toString(), sale.buyer.publicKey.toString());
+    });
+  });
+
+  describe("caveats", () => {
+    it("freezes dealings on a title until the caveator withdraws the caveat", async () => {
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("202888", "20288");
+      const caveatPDA = await lodgeCaveat(
+        counterparty,
+        titleDeedPDA,
+        { caveat: {} },
+        "Purchaser's interest under an unregistered sale agreement"
+      );
+
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.activeCaveats, 1);
+
+      // the caveat shows up on a search of the title
+      await searchTitleDeedByNumber(
+        counterparty,
+        "202888",
+        getTitleNumberLookupPDA("202888"),
+        titleDeedPDA,
+        counterpartyPDA
+      );
+      const searchCertificate = await program.account.searchCertificate.fetch(
+        getSearchCertificatePDA(titleDeedPDA, counterparty.publicKey)
+      );
+      assert.equal(searchCertificate.activeCaveats, 1);
+
+      const titleForSalePDA = getTitleForSalePDA(titleDeedPDA, owner.publicKey);
+      try {
+        await markTitleForSale(owner, new BN(100_000_000), titleDeedPDA, ownerPDA, titleForSalePDA);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "TitleHasCaveat",
+          `Expected TitleHasCaveat error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      await program.methods
+        .withdrawCaveat()
+        .accounts({
+          authority: counterparty.publicKey,
+          titleDeed: titleDeedPDA,
+          caveat: caveatPDA,
+          protocolState,
+        })
+        .signers([counterparty])
+        .rpc();
+
+      const caveat = await program.account.caveat.fetch(caveatPDA);
+      assert.ok("withdrawn" in caveat.status, "Caveat should be withdrawn");
+      assert.equal(caveat.liftedBy?.toString(), counterparty.publicKey.toString());
+
+      await markTitleForSale(owner, new BN(100_000_000), titleDeedPDA, ownerPDA, titleForSalePDA);
+      const titleDeedAfter = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeedAfter.isForSale, true);
+    });
+
+    it("rejects signing an agreement once a caveat is lodged against the title", async () => {
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("405111", "40511");
+      const price = new BN(100_000_000);
+      const titleForSalePDA = getTitleForSalePDA(titleDeedPDA, owner.publicKey);
+      const agreementPDA = getAgreementPDA(
+        owner.publicKey,
+        counterparty.publicKey,
+        titleDeedPDA,
+        price
+      );
+
+      await markTitleForSale(owner, price, titleDeedPDA, ownerPDA, titleForSalePDA);
+      await searchTitleDeedByNumber(
+        counterparty,
+        "405111",
+        getTitleNumberLookupPDA("405111"),
+        titleDeedPDA,
+        counterpartyPDA
+      );
+      await makeAgreement(
+        owner,
+        price,
+        titleDeedPDA,
+        titleForSalePDA,
+        ownerPDA,
+        counterpartyPDA,
+        getSearchCertificatePDA(titleDeedPDA, counterparty.publicKey),
+        agreementPDA,
+        getAgreementIndexPDA(titleDeedPDA)
+      );
+      await lodgeCaveat(
+        counterparty,
+        titleDeedPDA,
+        { caveat: {} },
+        "Purchaser's interest under an unregistered sale agreement"
+      );
+
+      try {
+        await signAgreement(counterparty, price, titleDeedPDA, agreementPDA);
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "TitleHasCaveat",
+          `Expected TitleHasCaveat error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+    });
+
+    it("only lets a registrar remove a court order", async () => {
+      const { counterparty, titleDeedPDA } = await setupRegisteredTitle("202999", "20299");
+      const caveatPDA = await lodgeCaveat(
+        counterparty,
+        titleDeedPDA,
+        { courtOrder: {} },
+        "Inhibition order, ELC case 12 of 2025"
+      );
+
+      try {
+        await program.methods
+          .withdrawCaveat()
+          .accounts({
+            authority: counterparty.publicKey,
+            titleDeed: titleDeedPDA,
+            caveat: caveatPDA,
+            protocolState,
+          })
+          .signers([counterparty])
+          .rpc();
+        assert.fail("Expected transaction to fail");
+      } catch (error) {
+        assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+        const anchorError = error as anchor.AnchorError;
+        assert.equal(
+          anchorError.error?.errorCode?.code,
+          "CaveatIsCourtOrder",
+          `Expected CaveatIsCourtOrder error, got: ${anchorError.error?.errorCode?.code}`
+        );
+      }
+
+      await program.methods
+        .removeCaveat("Order discharged on appeal")
+        .accounts({
+          authority: registrar2.publicKey,
+          registrar: registrar2PDA,
+          titleDeed: titleDeedPDA,
+          caveat: caveatPDA,
+          protocolState,
+        })
+        .signers([registrar2])
+        .rpc();
+
+      const caveat = await program.account.caveat.fetch(caveatPDA);
+      assert.ok("removed" in caveat.status, "Caveat should be removed");
+      assert.equal(caveat.liftingReason, "Order discharged on appeal");
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.activeCaveats, 0);
+    });
+  });
+
+  describe("leases", () => {
+    it("keeps a registered lease on the title after the freehold changes hands", async () => {
+      const { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA } =
+        await setupRegisteredTitle("303111", "30311");
+      const lessee = anchor.web3.Keypair.generate();
+      const leasePDA = getLeasePDA(titleDeedPDA, 1);
+      const termStart = Math.floor(Date.now() / 1000);
+      const termEnd = termStart + 99 * 365 * 24 * 60 * 60;
+
+      await program.methods
+        .registerLease(
+          lessee.publicKey,
+          new BN(termStart),
+          new BN(termEnd),
+          new BN(1_000_000),
+          "Commercial - petrol station"
+        )
+        .accounts({
+          authority: registrar2.publicKey,
+          registrar: registrar2PDA,
+          owner: owner.publicKey,
+          titleDeed: titleDeedPDA,
+          lease: leasePDA,
+          protocolState,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([registrar2, owner])
+        .rpc();
+
+      const lease = await program.account.lease.fetch(leasePDA);
+      assert.equal(lease.lessor.toString(), owner.publicKey.toString());
+      assert.equal(lease.lessee.toString(), lessee.publicKey.toString());
+      assert.equal(lease.termEnd.toNumber(), termEnd);
+      assert.equal(lease.permittedUse, "Commercial - petrol station");
+
+      // the freehold is gifted away - the lease still binds the new owner
+      await initiateGiftTransfer(owner, ownerPDA, titleDeedPDA, counterpartyPDA);
+      await program.methods
+        .acceptGiftTransfer()
+        .accounts({
+          authority: counterparty.publicKey,
+          titleDeed: titleDeedPDA,
+          giftTransfer: getGiftTransferPDA(titleDeedPDA),
+          protocolState,
+        })
+        .signers([counterparty])
+        .rpc();
+      await approveGiftTransfer(registrar2, registrar2PDA, titleDeedPDA, owner.publicKey, ownerPDA);
+
+      const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+      assert.equal(titleDeed.owner.authority.toString(), counterparty.publicKey.toString());
+      assert.deepEqual(
+        titleDeed.leases.map((registered) => registered.lease.toString()),
+        [leasePDA.toString()]
+      );
+
+      await searchTitleDeedByNumber(
+        counterparty,
+        "303111",
+        getTitleNumberLookupPDA("303111"),
+        titleDeedPDA,
+        counterpartyPDA
+      );
+      const searchCertificate = await program.account.searchCertificate.fetch(
+        getSearchCertificatePDA(titleDeedPDA, counterparty.publicKey)
+      );
+      assert.deepEqual(
+        searchCertificate.leases.map((lease) => lease.toString()),
+        [leasePDA.toString()]
+      );
+    });
+
+    it("does not subdivide or amalgamate a title while a lease is in force", async () => {
+      const { owner, ownerPDA, titleDeedPDA } = await setupRegisteredTitle("404111", "40411");
+      const otherTitleDeedPDA = await registerAdditionalTitle(owner, ownerPDA, "404112");
+      const termStart = Math.floor(Date.now() / 1000);
+      await registerLease(
+        owner,
+        titleDeedPDA,
+        anchor.web3.Keypair.generate().publicKey,
+        termStart,
+        termStart + 99 * 365 * 24 * 60 * 60
+      );
+
+      for (const deal of [
+        () =>
+          subdivideTitleDeed(owner, titleDeedPDA, [
+            { titleNumber: "404111-1", acreage: 5 },
+            { titleNumber: "404111-2", acreage: 5 },
+          ]),
+        () => amalgamateTitleDeeds(owner, "404113", [titleDeedPDA, otherTitleDeedPDA]),
+      ]) {
+        try {
+          await deal();
+          assert.fail("Expected transaction to fail");
+        } catch (error) {
+          assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+          const anchorError = error as anchor.AnchorError;
+          assert.equal(
+            anchorError.error?.errorCode?.code,
+            "TitleIsLeased",
+            `Expected TitleIsLeased error, got: ${anchorError.error?.errorCode?.code}`
+          );
+        }
+      }
+    });
+  });
+
+  describe("easements", () => {
+    it("links both titles to a registered right of way that survives a transfer", async () => {
+      const dominant = await setupRegisteredTitle("303222", "30322");
+      const servient = await setupRegisteredTitle("303333", "30333");
+      const easementPDA = getEasementPDA(servient.titleDeedPDA, 1);
+
+      await program.methods
+        .registerEasement({ rightOfWay: {} }, "6m access road along the northern boundary")
+        .accounts({
+          authority: registrar2.publicKey,
+          registrar: registrar2PDA,
+          dominantOwner: dominant.owner.publicKey,
+          servientOwner: servient.owner.publicKey,
+          dominantTitle: dominant.titleDeedPDA,
+          servientTitle: servient.titleDeedPDA,
+          easement: easementPDA,
+          protocolState,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([registrar2, dominant.owner, servient.owner])
+        .rpc();
+
+      const easement = await program.account.easement.fetch(easementPDA);
+      assert.equal(easement.dominantTitle.toString(), dominant.titleDeedPDA.toString());
+      assert.equal(easement.servientTitle.toString(), servient.titleDeedPDA.toString());
+      assert.ok("rightOfWay" in easement.kind, "Easement should be a right of way");
+
+      // the burdened parcel is gifted away - the easement stays on both titles
+      await initiateGiftTransfer(
+        servient.owner,
+        servient.ownerPDA,
+        servient.titleDeedPDA,
+        servient.counterpartyPDA
+      );
+      await program.methods
+        .acceptGiftTransfer()
+        .accounts({
+          authority: servient.counterparty.publicKey,
+          titleDeed: servient.titleDeedPDA,
+          giftTransfer: getGiftTransferPDA(servient.titleDeedPDA),
+          protocolState,
+        })
+        .signers([servient.counterparty])
+        .rpc();
+      await approveGiftTransfer(
+        registrar2,
+        registrar2PDA,
+        servient.titleDeedPDA,
+        servient.owner.publicKey,
+        servient.ownerPDA
+      );
+
+      for (const { titleNumber, titleDeedPDA } of [
+        { titleNumber: "303222", titleDeedPDA: dominant.titleDeedPDA },
+        { titleNumber: "303333", titleDeedPDA: servient.titleDeedPDA },
+      ]) {
+        await searchTitleDeedByNumber(
+          dominant.counterparty,
+          titleNumber,
+          getTitleNumberLookupPDA(titleNumber),
+          titleDeedPDA,
+          dominant.counterpartyPDA
+        );
+        const searchCertificate = await program.account.searchCertificate.fetch(
+          getSearchCertificatePDA(titleDeedPDA, dominant.counterparty.publicKey)
+        );
+        assert.deepEqual(
+          searchCertificate.easements.map((e) => e.toString()),
+          [easementPDA.toString()]
+        );
+      }
+    });
+
+    it("does not subdivide or amalgamate a title with easements registered against it", async () => {
+      const dominant = await setupRegisteredTitle("404222", "40422");
+      const servient = await setupRegisteredTitle("404333", "40433");
+      const otherTitleDeedPDA = await registerAdditionalTitle(
+        servient.owner,
+        servient.ownerPDA,
+        "404334"
+      );
+
+      await program.methods
+        .registerEasement({ wayleave: {} }, "Power line along the eastern boundary")
+        .accounts({
+          authority: registrar2.publicKey,
+          registrar: registrar2PDA,
+          dominantOwner: dominant.owner.publicKey,
+          servientOwner: servient.owner.publicKey,
+          dominantTitle: dominant.titleDeedPDA,
+          servientTitle: servient.titleDeedPDA,
+          easement: getEasementPDA(servient.titleDeedPDA, 1),
+          protocolState,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([registrar2, dominant.owner, servient.owner])
+        .rpc();
+
+      for (const deal of [
+        () =>
+          subdivideTitleDeed(servient.owner, servient.titleDeedPDA, [
+            { titleNumber: "404333-1", acreage: 5 },
+            { titleNumber: "404333-2", acreage: 5 },
+          ]),
+        () =>
+          amalgamateTitleDeeds(servient.owner, "404335", [
+            servient.titleDeedPDA,
+            otherTitleDeedPDA,
+          ]),
+      ]) {
+        try {
+          await deal();
+          assert.fail("Expected transaction to fail");
+        } catch (error) {
+          assert.ok(error instanceof anchor.AnchorError, "Expected AnchorError");
+          const anchorError = error as anchor.AnchorError;
+          assert.equal(
+            anchorError.error?.errorCode?.code,
+            "TitleHasEasements",
+            `Expected TitleHasEasements error, got: ${anchorError.error?.errorCode?.code}`
+          );
+        }
+      }
+    });
+  });
+
+  // helpers
+  const airdrop = async (publicKey: anchor.web3.PublicKey, amount: number) => {
+    const sig = await program.provider.connection.requestAirdrop(
+      publicKey,
+      amount
+    );
+    await program.provider.connection.confirmTransaction(sig, "confirmed");
+  };
+
+  // get PDA for the protocol
+  const getProtocolAddress = (programID: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [anchor.utils.bytes.utf8.encode("protocol_state")],
+      programID
+    );
+  };
+
+  const getRegistrarPDA = (authority: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("registrar"), authority.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const getUserAddress = (idNumber: string, authority: PublicKey) => {
+    const utf8Bytes = Buffer.from(idNumber, "utf-8");
+    const hash = crypto.createHash("sha256").update(utf8Bytes).digest();
+    const idNumberSeed = new Uint8Array(hash);
+
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("person"), idNumberSeed, authority.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const getIdNumberClaimPDA = (idNumber: string) => {
+    const utf8Bytes = Buffer.from(idNumber, "utf-8");
+    const hash = crypto.createHash("sha256").update(utf8Bytes).digest();
+    const idNumberSeed = new Uint8Array(hash);
+
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("id_number_claim"), idNumberSeed],
+      program.programId
+    )[0];
+  };
+
+  const getTitleDeedPDA = (titleNumber: string) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("title_deed"), Buffer.from(titleNumber)],
+      program.programId
+    )[0];
+  };
+
+  const getTitleForSalePDA = (titleDeed: PublicKey, seller: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("title_for_sale"), seller.toBuffer(), titleDeed.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const getTitleNumberLookupPDA = (titleNumber: string) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("title_number_lookup"), Buffer.from(titleNumber)],
+      program.programId
+    )[0];
+  };
+
+  const getSearchCertificatePDA = (titleDeed: PublicKey, searcher: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("search_certificate"), titleDeed.toBuffer(), searcher.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const getAgreementPDA = (
+    seller: PublicKey,
+    buyer: PublicKey,
+    titleDeed: PublicKey,
+    price: BN
+  ) => {
+    // price.to_le_bytes() in Rust returns 8 bytes in little-endian format
+    const priceBuffer = Buffer.allocUnsafe(8);
+    price.toArrayLike(Buffer, "le", 8).copy(priceBuffer);
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("agreement"),
+        seller.toBuffer(),
+        buyer.toBuffer(),
+        titleDeed.toBuffer(),
+        priceBuffer,
+      ],
+      program.programId
+    )[0];
+  };
+
+  const getAgreementIndexPDA = (titleDeed: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("agreement_index"), titleDeed.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const getEscrowPDA = (agreement: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), agreement.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const getDepositPDA = (escrow: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("deposit"), escrow.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const getProposalPDA = (id: BN) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), id.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  };
+
+  const getOwnershipHistoryPDA = (
+    titleDeed: PublicKey,
+    sequenceNumber: number
+  ) => {
+    const sequenceBuffer = Buffer.allocUnsafe(8);
+    sequenceBuffer.writeBigUInt64LE(BigInt(sequenceNumber), 0);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("ownership_history"), titleDeed.toBuffer(), sequenceBuffer],
+      program.programId
+    )[0];
+  };
+
+  const getAgreementRecordPDA = (
+    titleDeed: PublicKey,
+    sequenceNumber: number
+  ) => {
+    const sequenceBuffer = Buffer.allocUnsafe(8);
+    sequenceBuffer.writeBigUInt64LE(BigInt(sequenceNumber), 0);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("agreement_record"), titleDeed.toBuffer(), sequenceBuffer],
+      program.programId
+    )[0];
+  };
+
+  // the record written when the title's current agreement ends
+  const getNextAgreementRecordPDA = async (titleDeed: PublicKey) => {
+    const titleDeedAccount = await program.account.titleDeed.fetch(titleDeed);
+    return getAgreementRecordPDA(
+      titleDeed,
+      titleDeedAccount.totalAgreements.toNumber()
+    );
+  };
+
+  // Instruction helper functions
+  const createUserAccount = async (
+    authority: anchor.web3.Keypair,
+    firstName: string,
+    lastName: string,
+    idNumber: string,
+    phoneNumber: string,
+    userPDA: PublicKey,
+    idNumberClaimPDA: PublicKey
+  ) => {
+    await program.methods
+      .createUserAccount(firstName, lastName, idNumber, phoneNumber)
+      .accounts({
+        authority: authority.publicKey,
+        user: userPDA,
+        idNumberClaim: idNumberClaimPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  };
+
+  const createProposal = async (
+    authority: anchor.web3.Keypair,
+    action: any
+  ): Promise<PublicKey> => {
+    const protocol = await program.account.protocolState.fetch(protocolState);
+    const proposalPDA = getProposalPDA(protocol.proposalCount);
+    await program.methods
+      .createProposal(action)
+      .accounts({
+        authority: authority.publicKey,
+        protocolState,
+        proposal: proposalPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    return proposalPDA;
+  };
+
+  const approveProposal = async (
+    authority: anchor.web3.Keypair,
+    proposalPDA: PublicKey
+  ) => {
+    await program.methods
+      .approveProposal()
+      .accounts({
+        authority: authority.publicKey,
+        protocolState,
+        proposal: proposalPDA,
+      })
+      .signers([authority])
+      .rpc();
+  };
+
+  // admin1 proposes an action and admin2 approves it (threshold of 2 admins)
+  const proposeAndApprove = async (action: any): Promise<PublicKey> => {
+    const proposalPDA = await createProposal(admin1, action);
+    await approveProposal(admin2, proposalPDA);
+    return proposalPDA;
+  };
+
+  const addRegistrar = async (
+    registrarAuthority: PublicKey,
+    firstName: string,
+    lastName: string,
+    idNumber: string,
+    districts: string[],
+    registrarPDA: PublicKey
+  ) => {
+    const proposalPDA = await proposeAndApprove({
+      addRegistrar: { registrar: registrarAuthority, districts },
+    });
+    await program.methods
+      .addRegistrar(registrarAuthority, firstName, lastName, idNumber)
+      .accounts({
+        authority: admin1.publicKey,
+        registrar: registrarPDA,
+        admin: admin1PDA,
+        protocolState,
+        proposal: proposalPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin1])
+      .rpc();
+  };
+
+  const assignTitleDeedToOwner = async (
+    registrar: anchor.web3.Keypair,
+    registrarPDA: PublicKey,
+    newOwnerAddress: PublicKey,
+    titleNumber: string,
+    location: string,
+    acreage: number,
+    districtLandRegistry: string,
+    registryMapsheetNumber: BN,
+    titleDeedPDA: PublicKey,
+    ownerPDA: PublicKey
+  ) => {
+    const ownershipHistoryPDA = getOwnershipHistoryPDA(titleDeedPDA, 0);
+    await program.methods
+      .assignTitleDeedToOwner(
+        newOwnerAddress,
+        titleNumber,
+        location,
+        acreage,
+        districtLandRegistry,
+        registryMapsheetNumber
+      )
+      .accounts({
+        authority: registrar.publicKey,
+        registrar: registrarPDA,
+        titleDeed: titleDeedPDA,
+        owner: ownerPDA,
+        ownershipHistory: ownershipHistoryPDA,
+        titleNumberLookup: getTitleNumberLookupPDA(titleNumber),
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([registrar])
+      .rpc();
+  };
+
+  const markTitleForSale = async (
+    authority: anchor.web3.Keypair,
+    price: BN,
+    titleDeedPDA: PublicKey,
+    sellerPDA: PublicKey,
+    titleForSalePDA: PublicKey,
+    coSigners: anchor.web3.Keypair[] = []
+  ) => {
+    await program.methods
+      .markTitleForSale(price)
+      .accounts({
+        authority: authority.publicKey,
+        titleDeed: titleDeedPDA,
+        seller: sellerPDA,
+        deathRecord: getDeathRecordPDA(authority.publicKey),
+        titleForSale: titleForSalePDA,
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(coSignerAccounts(coSigners))
+      .signers([authority, ...coSigners])
+      .rpc();
+  };
 
   const searchTitleDeedByNumber = async (
     authority: anchor.web3.Keypair,
-    titleNumber: string,
-    titleNumberLookupPDA: PublicKey,
+    titleNumber: string,
+    titleNumberLookupPDA: PublicKey,
+    titleDeedPDA: PublicKey,
+    searchedByPDA: PublicKey
+  ) => {
+    await program.methods
+      .searchTitleDeedByNumber(titleNumber)
+      .accounts({
+        authority: authority.publicKey,
+        titleNumberLookup: titleNumberLookupPDA,
+        titleDeed: titleDeedPDA,
+        searchCertificate: getSearchCertificatePDA(titleDeedPDA, authority.publicKey),
+        searchedBy: searchedByPDA,
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  };
+
+  const makeAgreement = async (
+    authority: anchor.web3.Keypair,
+    price: BN,
+    titleDeedPDA: PublicKey,
+    titleForSalePDA: PublicKey,
+    sellerPDA: PublicKey,
+    buyerPDA: PublicKey,
+    searchCertificatePDA: PublicKey,
+    agreementPDA: PublicKey,
+    agreementIndexPDA: PublicKey,
+    coSigners: anchor.web3.Keypair[] = []
+  ) => {
+    await program.methods
+      .makeAgreement(price)
+      .accounts({
+        authority: authority.publicKey,
+        titleDeed: titleDeedPDA,
+        titleForSale: titleForSalePDA,
+        seller: sellerPDA,
+        deathRecord: getDeathRecordPDA(authority.publicKey),
+        buyer: buyerPDA,
+        searchCertificate: searchCertificatePDA,
+        agreement: agreementPDA,
+        agreementIndex: agreementIndexPDA,
+        protocolState,
+      })
+      .remainingAccounts(coSignerAccounts(coSigners))
+      .signers([authority, ...coSigners])
+      .rpc();
+  };
+
+  const signAgreement = async (
+    authority: anchor.web3.Keypair,
+    price: BN,
+    titleDeedPDA: PublicKey,
+    agreementPDA: PublicKey
+  ) => {
+    await program.methods
+      .signAgreement(price)
+      .accounts({
+        authority: authority.publicKey,
+        titleDeed: titleDeedPDA,
+        agreement: agreementPDA,
+        protocolState,
+      })
+      .signers([authority])
+      .rpc();
+  };
+
+  const createEscrow = async (
+    authority: anchor.web3.Keypair,
+    titleDeedPDA: PublicKey,
+    agreementPDA: PublicKey,
+    sellerPDA: PublicKey,
+    buyerPDA: PublicKey,
+    escrowPDA: PublicKey,
+    coSigners: anchor.web3.Keypair[] = []
+  ) => {
+    await program.methods
+      .createEscrow()
+      .accounts({
+        authority: authority.publicKey,
+        titleDeed: titleDeedPDA,
+        agreement: agreementPDA,
+        seller: sellerPDA,
+        deathRecord: getDeathRecordPDA(authority.publicKey),
+        buyer: buyerPDA,
+        escrow: escrowPDA,
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(coSignerAccounts(coSigners))
+      .signers([authority, ...coSigners])
+      .rpc();
+  };
+
+  const cancelEscrow = async (
+    authority: anchor.web3.Keypair,
+    sale: EscrowSale,
+    depositPDA: PublicKey | null,
+    reason: string = "Sale called off"
+  ) => {
+    await program.methods
+      .cancelEscrow(reason)
+      .accounts({
+        authority: authority.publicKey,
+        escrow: sale.escrowPDA,
+        titleDeed: sale.titleDeedPDA,
+        agreement: sale.agreementPDA,
+        agreementIndex: sale.agreementIndexPDA,
+        agreementRecord: await getNextAgreementRecordPDA(sale.titleDeedPDA),
+        deposit: depositPDA,
+        sellerAuthority: sale.seller.publicKey,
+        buyerAuthority: sale.buyer.publicKey,
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  };
+
+  const rejectEscrow = async (
+    authority: anchor.web3.Keypair,
+    registrarPDA: PublicKey,
+    sale: EscrowSale,
+    reason: any,
+    note: string
+  ) => {
+    await program.methods
+      .rejectEscrow(reason, note)
+      .accounts({
+        authority: authority.publicKey,
+        registrar: registrarPDA,
+        escrow: sale.escrowPDA,
+        titleDeed: sale.titleDeedPDA,
+        agreement: sale.agreementPDA,
+        agreementIndex: sale.agreementIndexPDA,
+        agreementRecord: await getNextAgreementRecordPDA(sale.titleDeedPDA),
+        deposit: sale.depositPDA,
+        sellerAuthority: sale.seller.publicKey,
+        buyerAuthority: sale.buyer.publicKey,
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  };
+
+  const depositPaymentToEscrow = async (
+    authority: anchor.web3.Keypair,
+    amount: BN,
+    buyerPDA: PublicKey,
+    sellerPDA: PublicKey,
+    escrowPDA: PublicKey,
+    agreementPDA: PublicKey,
+    depositPDA: PublicKey
+  ) => {
+    await program.methods
+      .depositPaymentToEscrow(amount)
+      .accounts({
+        authority: authority.publicKey,
+        buyer: buyerPDA,
+        seller: sellerPDA,
+        escrow: escrowPDA,
+        agreement: agreementPDA,
+        deposit: depositPDA,
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  };
+
+  const getGiftTransferPDA = (titleDeed: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("gift_transfer"), titleDeed.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const initiateGiftTransfer = async (
+    authority: anchor.web3.Keypair,
+    donorPDA: PublicKey,
     titleDeedPDA: PublicKey,
-    searchedByPDA: PublicKey
+    recipientPDA: PublicKey,
+    shareBps: number = 10000
   ) => {
     await program.methods
-      .searchTitleDeedByNumber(titleNumber)
+      .initiateGiftTransfer(shareBps)
       .accounts({
         authority: authority.publicKey,
-        titleNumberLookup: titleNumberLookupPDA,
         titleDeed: titleDeedPDA,
-        searchedBy: searchedByPDA,
+        donor: donorPDA,
+        deathRecord: getDeathRecordPDA(authority.publicKey),
+        recipient: recipientPDA,
+        giftTransfer: getGiftTransferPDA(titleDeedPDA),
+        protocolState,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
   };
 
-  const makeAgreement = async (
+  const approveGiftTransfer = async (
+    registrar: anchor.web3.Keypair,
+    registrarPDA: PublicKey,
+    titleDeedPDA: PublicKey,
+    donorAuthority: PublicKey,
+    donorPDA: PublicKey
+  ) => {
+    const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+    await program.methods
+      .approveGiftTransfer()
+      .accounts({
+        authority: registrar.publicKey,
+        registrar: registrarPDA,
+        titleDeed: titleDeedPDA,
+        giftTransfer: getGiftTransferPDA(titleDeedPDA),
+        ownershipHistory: getOwnershipHistoryPDA(
+          titleDeedPDA,
+          titleDeed.totalTransfers.toNumber() + 1
+        ),
+        donorAuthority,
+        donor: donorPDA,
+        deathRecord: getDeathRecordPDA(donorAuthority),
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([registrar])
+      .rpc();
+  };
+
+  const getDeathRecordPDA = (authority: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("death_record"), authority.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const getSuccessionPDA = (titleDeed: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("succession"), titleDeed.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const recordSuccession = async (
+    titleDeedPDA: PublicKey,
+    deceasedPDA: PublicKey,
+    courtGrantReference: string,
+    administrator: PublicKey,
+    beneficiaries: PublicKey[]
+  ) => {
+    const deceased = await program.account.user.fetch(deceasedPDA);
+    await program.methods
+      .recordSuccession(courtGrantReference, administrator, beneficiaries)
+      .accounts({
+        authority: registrar2.publicKey,
+        registrar: registrar2PDA,
+        titleDeed: titleDeedPDA,
+        deceased: deceasedPDA,
+        deathRecord: getDeathRecordPDA(deceased.authority),
+        succession: getSuccessionPDA(titleDeedPDA),
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([registrar2])
+      .rpc();
+  };
+
+  const transferToBeneficiary = async (
+    administrator: anchor.web3.Keypair,
+    titleDeedPDA: PublicKey,
+    beneficiaryPDA: PublicKey,
+    shareBps: number
+  ) => {
+    const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+    await program.methods
+      .transferToBeneficiary(shareBps)
+      .accounts({
+        authority: registrar2.publicKey,
+        registrar: registrar2PDA,
+        administrator: administrator.publicKey,
+        titleDeed: titleDeedPDA,
+        succession: getSuccessionPDA(titleDeedPDA),
+        beneficiary: beneficiaryPDA,
+        ownershipHistory: getOwnershipHistoryPDA(
+          titleDeedPDA,
+          titleDeed.totalTransfers.toNumber() + 1
+        ),
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([registrar2, administrator])
+      .rpc();
+  };
+
+  // co-owners other than the registered owner sign dealings on a co-owned title
+  const coSignerAccounts = (coSigners: anchor.web3.Keypair[]) => {
+    return coSigners.map((coSigner) => ({
+      pubkey: coSigner.publicKey,
+      isSigner: true,
+      isWritable: false,
+    }));
+  };
+
+  const registerCoOwnership = async (
+    owner: anchor.web3.Keypair,
+    titleDeedPDA: PublicKey,
+    tenure: any,
+    coOwners: { authority: PublicKey; shareBps: number }[]
+  ) => {
+    await program.methods
+      .registerCoOwnership(tenure, coOwners)
+      .accounts({
+        authority: registrar2.publicKey,
+        registrar: registrar2PDA,
+        owner: owner.publicKey,
+        titleDeed: titleDeedPDA,
+        protocolState,
+      })
+      .signers([registrar2, owner])
+      .rpc();
+  };
+
+  const markShareForSale = async (
     authority: anchor.web3.Keypair,
+    shareBps: number,
     price: BN,
     titleDeedPDA: PublicKey,
-    titleForSalePDA: PublicKey,
-    sellerPDA: PublicKey,
-    buyerPDA: PublicKey,
-    titleNumberLookupPDA: PublicKey,
-    agreementPDA: PublicKey,
-    agreementIndexPDA: PublicKey
+    sellerPDA: PublicKey
   ) => {
     await program.methods
-      .makeAgreement(price)
+      .markShareForSale(shareBps, price)
       .accounts({
         authority: authority.publicKey,
         titleDeed: titleDeedPDA,
-        titleForSale: titleForSalePDA,
         seller: sellerPDA,
-        buyer: buyerPDA,
-        titleNumberLookup: titleNumberLookupPDA,
-        agreement: agreementPDA,
-        agreementIndex: agreementIndexPDA,
+        deathRecord: getDeathRecordPDA(authority.publicKey),
+        titleForSale: getTitleForSalePDA(titleDeedPDA, authority.publicKey),
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
   };
 
-  const signAgreement = async (
-    authority: anchor.web3.Keypair,
-    price: BN,
-    titleDeedPDA: PublicKey,
-    agreementPDA: PublicKey
-  ) => {
+  const unlistShare = async (authority: anchor.web3.Keypair, titleDeedPDA: PublicKey) => {
     await program.methods
-      .signAgreement(price)
+      .unlistShare()
       .accounts({
         authority: authority.publicKey,
         titleDeed: titleDeedPDA,
-        agreement: agreementPDA,
+        titleForSale: getTitleForSalePDA(titleDeedPDA, authority.publicKey),
+        agreementIndex: getAgreementIndexPDA(titleDeedPDA),
+        protocolState,
       })
       .signers([authority])
       .rpc();
   };
 
-  const createEscrow = async (
-    authority: anchor.web3.Keypair,
+  // each parcel's title deed, title number lookup and first ownership history entry, in order
+  const parcelAccounts = (parcels: { titleNumber: string; acreage: number }[]) =>
+    parcels.flatMap(({ titleNumber }) => {
+      const parcelTitleDeedPDA = getTitleDeedPDA(titleNumber);
+      return [
+        parcelTitleDeedPDA,
+        getTitleNumberLookupPDA(titleNumber),
+        getOwnershipHistoryPDA(parcelTitleDeedPDA, 0),
+      ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }));
+    });
+
+  const subdivideTitleDeed = async (
+    owner: anchor.web3.Keypair,
     titleDeedPDA: PublicKey,
-    agreementPDA: PublicKey,
-    sellerPDA: PublicKey,
-    buyerPDA: PublicKey,
-    escrowPDA: PublicKey
+    parcels: { titleNumber: string; acreage: number }[],
+    coSigners: anchor.web3.Keypair[] = []
   ) => {
     await program.methods
-      .createEscrow()
+      .subdivideTitleDeed(parcels)
       .accounts({
-        authority: authority.publicKey,
+        authority: registrar2.publicKey,
+        registrar: registrar2PDA,
+        owner: owner.publicKey,
         titleDeed: titleDeedPDA,
-        agreement: agreementPDA,
-        seller: sellerPDA,
-        buyer: buyerPDA,
-        escrow: escrowPDA,
+        protocolState,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
-      .signers([authority])
+      .remainingAccounts([...parcelAccounts(parcels), ...coSignerAccounts(coSigners)])
+      .signers([registrar2, owner, ...coSigners])
       .rpc();
   };
 
-  const depositPaymentToEscrow = async (
-    authority: anchor.web3.Keypair,
+  const registerLease = async (
+    owner: anchor.web3.Keypair,
+    titleDeedPDA: PublicKey,
+    lessee: PublicKey,
+    termStart: number,
+    termEnd: number
+  ) => {
+    const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+    await program.methods
+      .registerLease(lessee, new BN(termStart), new BN(termEnd), new BN(1_000_000), "Residential")
+      .accounts({
+        authority: registrar2.publicKey,
+        registrar: registrar2PDA,
+        owner: owner.publicKey,
+        titleDeed: titleDeedPDA,
+        lease: getLeasePDA(titleDeedPDA, titleDeed.totalLeases.toNumber() + 1),
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([registrar2, owner])
+      .rpc();
+  };
+
+  const amalgamateTitleDeeds = async (
+    owner: anchor.web3.Keypair,
+    titleNumber: string,
+    sourceTitleDeedPDAs: PublicKey[],
+    coSigners: anchor.web3.Keypair[] = []
+  ) => {
+    const titleDeedPDA = getTitleDeedPDA(titleNumber);
+    await program.methods
+      .amalgamateTitleDeeds(titleNumber)
+      .accounts({
+        authority: registrar2.publicKey,
+        registrar: registrar2PDA,
+        owner: owner.publicKey,
+        titleDeed: titleDeedPDA,
+        titleNumberLookup: getTitleNumberLookupPDA(titleNumber),
+        ownershipHistory: getOwnershipHistoryPDA(titleDeedPDA, 0),
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        ...sourceTitleDeedPDAs.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+        ...coSignerAccounts(coSigners),
+      ])
+      .signers([registrar2, owner, ...coSigners])
+      .rpc();
+  };
+
+  // registers another title in Nakuru to an owner set up with `setupRegisteredTitle`
+  const registerAdditionalTitle = async (
+    owner: anchor.web3.Keypair,
+    ownerPDA: PublicKey,
+    titleNumber: string
+  ) => {
+    const titleDeedPDA = getTitleDeedPDA(titleNumber);
+    await assignTitleDeedToOwner(
+      registrar2,
+      registrar2PDA,
+      owner.publicKey,
+      titleNumber,
+      "Nakuru",
+      10,
+      "Nakuru",
+      new BN(123123),
+      titleDeedPDA,
+      ownerPDA
+    );
+    return titleDeedPDA;
+  };
+
+  const getChargePDA = (titleDeed: PublicKey, priority: number) => {
+    const priorityBuffer = Buffer.allocUnsafe(8);
+    priorityBuffer.writeBigUInt64LE(BigInt(priority), 0);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("charge"), titleDeed.toBuffer(), priorityBuffer],
+      program.programId
+    )[0];
+  };
+
+  // registers the next charge on a title for a lender, returning the charge PDA
+  const registerCharge = async (
+    owner: anchor.web3.Keypair,
+    lender: anchor.web3.Keypair,
+    titleDeedPDA: PublicKey,
     amount: BN,
-    buyerPDA: PublicKey,
-    sellerPDA: PublicKey,
-    escrowPDA: PublicKey,
-    agreementPDA: PublicKey,
-    depositPDA: PublicKey
+    coSigners: anchor.web3.Keypair[] = []
   ) => {
+    const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+    const chargePDA = getChargePDA(titleDeedPDA, titleDeed.totalCharges.toNumber() + 1);
     await program.methods
-      .depositPaymentToEscrow(amount)
+      .registerCharge(amount)
       .accounts({
-        authority: authority.publicKey,
-        buyer: buyerPDA,
-        seller: sellerPDA,
-        escrow: escrowPDA,
-        agreement: agreementPDA,
-        deposit: depositPDA,
+        authority: registrar2.publicKey,
+        registrar: registrar2PDA,
+        lender: lender.publicKey,
+        owner: owner.publicKey,
+        titleDeed: titleDeedPDA,
+        charge: chargePDA,
+        protocolState,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
-      .signers([authority])
+      .remainingAccounts(coSignerAccounts(coSigners))
+      .signers([registrar2, lender, owner, ...coSigners])
+      .rpc();
+    return chargePDA;
+  };
+
+  const getCaveatPDA = (titleDeed: PublicKey, sequenceNumber: number) => {
+    const sequenceBuffer = Buffer.allocUnsafe(8);
+    sequenceBuffer.writeBigUInt64LE(BigInt(sequenceNumber), 0);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("caveat"), titleDeed.toBuffer(), sequenceBuffer],
+      program.programId
+    )[0];
+  };
+
+  // lodges the next caveat on a title for a caveator, returning the caveat PDA
+  const lodgeCaveat = async (
+    caveator: anchor.web3.Keypair,
+    titleDeedPDA: PublicKey,
+    kind: any,
+    reason: string,
+    expiresAt: BN | null = null
+  ) => {
+    const titleDeed = await program.account.titleDeed.fetch(titleDeedPDA);
+    const caveatPDA = getCaveatPDA(titleDeedPDA, titleDeed.totalCaveats.toNumber() + 1);
+    await program.methods
+      .lodgeCaveat(kind, reason, expiresAt)
+      .accounts({
+        authority: registrar2.publicKey,
+        registrar: registrar2PDA,
+        caveator: caveator.publicKey,
+        titleDeed: titleDeedPDA,
+        caveat: caveatPDA,
+        protocolState,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([registrar2, caveator])
       .rpc();
+    return caveatPDA;
+  };
+
+  const getLeasePDA = (titleDeed: PublicKey, sequenceNumber: number) => {
+    const sequenceBuffer = Buffer.allocUnsafe(8);
+    sequenceBuffer.writeBigUInt64LE(BigInt(sequenceNumber), 0);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("lease"), titleDeed.toBuffer(), sequenceBuffer],
+      program.programId
+    )[0];
+  };
+
+  const getEasementPDA = (servientTitle: PublicKey, sequenceNumber: number) => {
+    const sequenceBuffer = Buffer.allocUnsafe(8);
+    sequenceBuffer.writeBigUInt64LE(BigInt(sequenceNumber), 0);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("easement"), servientTitle.toBuffer(), sequenceBuffer],
+      program.programId
+    )[0];
+  };
+
+  type RegisteredTitle = {
+    owner: anchor.web3.Keypair;
+    ownerPDA: PublicKey;
+    counterparty: anchor.web3.Keypair;
+    counterpartyPDA: PublicKey;
+    titleDeedPDA: PublicKey;
+  };
+
+  // registers a new title in Nakuru for a fresh owner, alongside a second user to deal with
+  const setupRegisteredTitle = async (
+    titleNumber: string,
+    idNumberPrefix: string
+  ): Promise<RegisteredTitle> => {
+    const owner = anchor.web3.Keypair.generate();
+    const counterparty = anchor.web3.Keypair.generate();
+    await airdrop(owner.publicKey, 100_000_000);
+    await airdrop(counterparty.publicKey, 100_000_000);
+
+    const ownerIdNumber = `${idNumberPrefix}1`;
+    const counterpartyIdNumber = `${idNumberPrefix}2`;
+    const ownerPDA = getUserAddress(ownerIdNumber, owner.publicKey);
+    const counterpartyPDA = getUserAddress(counterpartyIdNumber, counterparty.publicKey);
+    await createUserAccount(
+      owner,
+      "Title",
+      "Owner",
+      ownerIdNumber,
+      "700000003",
+      ownerPDA,
+      getIdNumberClaimPDA(ownerIdNumber)
+    );
+    await createUserAccount(
+      counterparty,
+      "Title",
+      "Counterparty",
+      counterpartyIdNumber,
+      "700000004",
+      counterpartyPDA,
+      getIdNumberClaimPDA(counterpartyIdNumber)
+    );
+
+    const titleDeedPDA = getTitleDeedPDA(titleNumber);
+    await assignTitleDeedToOwner(
+      registrar2,
+      registrar2PDA,
+      owner.publicKey,
+      titleNumber,
+      "Nakuru",
+      10,
+      "Nakuru",
+      new BN(123123),
+      titleDeedPDA,
+      ownerPDA
+    );
+
+    return { owner, ownerPDA, counterparty, counterpartyPDA, titleDeedPDA };
+  };
+
+  type EscrowSale = {
+    seller: anchor.web3.Keypair;
+    sellerPDA: PublicKey;
+    buyer: anchor.web3.Keypair;
+    buyerPDA: PublicKey;
+    titleDeedPDA: PublicKey;
+    agreementPDA: PublicKey;
+    agreementIndexPDA: PublicKey;
+    escrowPDA: PublicKey;
+    depositPDA: PublicKey;
+  };
+
+  // takes a new title from registration through to an escrow, optionally with the buyer's payment.
+  // `beforeListing` runs on the registered title before it is put up for sale
+  const setupEscrowSale = async (
+    titleNumber: string,
+    idNumberPrefix: string,
+    price: BN,
+    withPayment: boolean,
+    beforeListing?: (titleDeedPDA: PublicKey, seller: anchor.web3.Keypair) => Promise<void>
+  ): Promise<EscrowSale> => {
+    const seller = anchor.web3.Keypair.generate();
+    const buyer = anchor.web3.Keypair.generate();
+    await airdrop(seller.publicKey, 100_000_000);
+    await airdrop(buyer.publicKey, price.toNumber() + 100_000_000);
+
+    const sellerIdNumber = `${idNumberPrefix}1`;
+    const buyerIdNumber = `${idNumberPrefix}2`;
+    const sellerPDA = getUserAddress(sellerIdNumber, seller.publicKey);
+    const buyerPDA = getUserAddress(buyerIdNumber, buyer.publicKey);
+    await createUserAccount(
+      seller,
+      "Escrow",
+      "Seller",
+      sellerIdNumber,
+      "700000001",
+      sellerPDA,
+      getIdNumberClaimPDA(sellerIdNumber)
+    );
+    await createUserAccount(
+      buyer,
+      "Escrow",
+      "Buyer",
+      buyerIdNumber,
+      "700000002",
+      buyerPDA,
+      getIdNumberClaimPDA(buyerIdNumber)
+    );
+
+    const titleDeedPDA = getTitleDeedPDA(titleNumber);
+    const titleForSalePDA = getTitleForSalePDA(titleDeedPDA, seller.publicKey);
+    await assignTitleDeedToOwner(
+      registrar2,
+      registrar2PDA,
+      seller.publicKey,
+      titleNumber,
+      "Nakuru",
+      10,
+      "Nakuru",
+      new BN(123123),
+      titleDeedPDA,
+      sellerPDA
+    );
+    if (beforeListing) {
+      await beforeListing(titleDeedPDA, seller);
+    }
+    await markTitleForSale(seller, price, titleDeedPDA, sellerPDA, titleForSalePDA);
+    await searchTitleDeedByNumber(
+      buyer,
+      titleNumber,
+      getTitleNumberLookupPDA(titleNumber),
+      titleDeedPDA,
+      buyerPDA
+    );
+
+    const agreementPDA = getAgreementPDA(
+      seller.publicKey,
+      buyer.publicKey,
+      titleDeedPDA,
+      price
+    );
+    const agreementIndexPDA = getAgreementIndexPDA(titleDeedPDA);
+    const escrowPDA = getEscrowPDA(agreementPDA);
+    const depositPDA = getDepositPDA(escrowPDA);
+    await makeAgreement(
+      seller,
+      price,
+      titleDeedPDA,
+      titleForSalePDA,
+      sellerPDA,
+      buyerPDA,
+      getSearchCertificatePDA(titleDeedPDA, buyer.publicKey),
+      agreementPDA,
+      agreementIndexPDA
+    );
+    await signAgreement(buyer, price, titleDeedPDA, agreementPDA);
+    await createEscrow(seller, titleDeedPDA, agreementPDA, sellerPDA, buyerPDA, escrowPDA);
+    if (withPayment) {
+      await depositPaymentToEscrow(
+        buyer,
+        price,
+        buyerPDA,
+        sellerPDA,
+        escrowPDA,
+        agreementPDA,
+        depositPDA
+      );
+    }
+
+    return {
+      seller,
+      sellerPDA,
+      buyer,
+      buyerPDA,
+      titleDeedPDA,
+      agreementPDA,
+      agreementIndexPDA,
+      escrowPDA,
+      depositPDA,
+    };
   };
 });
@@ -38,6 +38,7 @@ export default function RegistrarComponent() {
   const [firstName, setFirstName] = useState("");
   const [lastName, setLastName] = useState("");
   const [idNumber, setIdNumber] = useState("");
+  const [districts, setDistricts] = useState("");
   const [walletAddress, setWalletAddress] = useState("");
   const [isDialogOpen, setIsDialogOpen] = useState(false);
   const [isAdmin, setIsAdmin] = useState<boolean | null>(null);
@@ -84,6 +85,11 @@ export default function RegistrarComponent() {
           firstName,
           lastName,
           idNumber,
+          // comma separated district land registries
+          districts: districts
+            .split(",")
+            .map((district) => district.trim())
+            .filter((district) => district.length > 0),
           walletAddress,
           adminAuthority,
         })
@@ -94,6 +100,7 @@ export default function RegistrarComponent() {
         setFirstName("");
         setLastName("");
         setIdNumber("");
+        setDistricts("");
         setWalletAddress("");
         setIsDialogOpen(false);
         // Refetch registrars list after successful creation
@@ -179,6 +186,21 @@ export default function RegistrarComponent() {
                       disabled={isSubmitting}
                     />
                   </div>
+                  <div className="grid gap-3">
+                    <label htmlFor="districts" className="text-sm font-bold">
+                      Districts
+                    </label>
+                    <Input
+                      className="bg-[#f5f1ec] border-[#d3aa7b]"
+                      id="districts"
+                      name="districts"
+                      required
+                      placeholder="Nairobi, Kiambu"
+                      value={districts}
+                      onChange={(e) => setDistricts(e.target.value)}
+                      disabled={isSubmitting}
+                    />
+                  </div>
                   <div className="grid gap-3">
                     <label htmlFor="walletAddress" className="text-sm font-bold">
                       Solana Wallet Address
@@ -87,9 +87,11 @@ export class RegistrarService {
 
   /**
    * Add a registrar - for admins only
+   * the registrar's districts are set by the proposal, which is created here if none is pending
    * @param firstName
    * @param lastName
    * @param idNumber
+   * @param districts
    * @param registrarAuthority
    * @param adminAuthority
    * @returns Promise<TransactionSignature>
@@ -98,12 +100,16 @@ export class RegistrarService {
     firstName: string,
     lastName: string,
     idNumber: string,
+    districts: string[],
     registrarAuthority: PublicKey,
     adminAuthority: PublicKey
   ): Promise<TransactionSignature> {
     const registrarPDA = getRegistrarPDA(registrarAuthority, this.programId);
     const adminPDA = getAdminPDA(adminAuthority, this.programId);
     const protocolStatePDA = getProtocolAddress(this.programId);
+    const proposalPDA =
+      (await this.findAddRegistrarProposal(registrarAuthority)) ??
+      (await this.proposeRegistrar(registrarAuthority, districts, adminAuthority));
     const tx = await this.program.methods
       .addRegistrar(registrarAuthority, firstName, lastName, idNumber)
       .accounts({
@@ -111,11 +117,57 @@ export class RegistrarService {
         registrar: registrarPDA,
         admin: adminPDA,
         protocolState: protocolStatePDA,
+        proposal: proposalPDA,
         systemProgram: SystemProgram.programId,
       } as any)
       .rpc();
     return tx;
   }
+
+  /**
+   * Propose a registrar and the districts they will serve - the proposer's approval is recorded
+   * @param registrarAuthority
+   * @param districts
+   * @param adminAuthority
+   * @returns Promise<PublicKey>
+   */
+  async proposeRegistrar(
+    registrarAuthority: PublicKey,
+    districts: string[],
+    adminAuthority: PublicKey
+  ): Promise<PublicKey> {
+    const protocolStatePDA = getProtocolAddress(this.programId);
+    const protocolState = await this.program.account.protocolState.fetch(protocolStatePDA);
+    const proposalPDA = getProposalPDA(protocolState.proposalCount.toNumber(), this.programId);
+    await this.program.methods
+      .createProposal({ addRegistrar: { registrar: registrarAuthority, districts } })
+      .accounts({
+        authority: adminAuthority,
+        protocolState: protocolStatePDA,
+        proposal: proposalPDA,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+    return proposalPDA;
+  }
+
+  /**
+   * Find the pending proposal approving a registrar address
+   * @param registrarAuthority
+   * @returns Promise<PublicKey | null>
+   */
+  private async findAddRegistrarProposal(registrarAuthority: PublicKey): Promise<PublicKey | null> {
+    const proposals = await this.program.account.proposal.all();
+    const proposal = proposals.find((p) => {
+      const account = p.account as any;
+      return (
+        account.executedAt === null &&
+        account.expiresAt.toNumber() >= Date.now() / 1000 &&
+        account.action.addRegistrar?.registrar.equals(registrarAuthority)
+      );
+    });
+    return proposal ? proposal.publicKey : null;
+  }
 }
 
 export class UserService {
@@ -218,8 +270,10 @@ export class TitleDeedService {
     registryMapsheetNumber: number
   ): Promise<TransactionSignature> {
     const registrarPDA = getRegistrarPDA(registrarAuthority, this.programId);
-    const titleDeedPDA = getTitleDeedPDA(newOwnerAddress, this.programId);
+    const titleDeedPDA = getTitleDeedPDA(titleNumber, this.programId);
     const ownershipHistoryPDA = getOwnershipHistoryPDA(titleDeedPDA, 0, this.programId);
+    const titleNumberLookupPDA = getTitleNumberLookupPDA(titleNumber, this.programId);
+    const protocolStatePDA = getProtocolAddress(this.programId);
 
     const tx = await this.program.methods
       .assignTitleDeedToOwner(
@@ -236,6 +290,8 @@ export class TitleDeedService {
         titleDeed: titleDeedPDA,
         owner: ownerUserPDA,
         ownershipHistory: ownershipHistoryPDA,
+        titleNumberLookup: titleNumberLookupPDA,
+        protocolState: protocolStatePDA,
         systemProgram: SystemProgram.programId,
       } as any)
       .rpc();
@@ -298,13 +354,26 @@ export const getIdNumberClaimPDA = (idNumber: string, programId: PublicKey): Pub
 
 /**
  * Get the PDA for a title deed
- * @param owner
+ * @param titleNumber
  * @param programId
  * @returns
  */
-export const getTitleDeedPDA = (owner: PublicKey, programId: PublicKey): PublicKey => {
+export const getTitleDeedPDA = (titleNumber: string, programId: PublicKey): PublicKey => {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("title_deed"), owner.toBuffer()],
+    [Buffer.from("title_deed"), Buffer.from(titleNumber, "utf-8")],
+    programId
+  )[0];
+};
+
+/**
+ * Get the PDA for a title number lookup
+ * @param titleNumber
+ * @param programId
+ * @returns
+ */
+export const getTitleNumberLookupPDA = (titleNumber: string, programId: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("title_number_lookup"), Buffer.from(titleNumber, "utf-8")],
     programId
   )[0];
 };
@@ -336,7 +405,20 @@ export const getOwnershipHistoryPDA = (
  * @returns
  */
 export const getProtocolAddress = (programId: PublicKey): PublicKey => {
-  return PublicKey.findProgramAddressSync([Buffer.from("land_registry")], programId)[0];
+  return PublicKey.findProgramAddressSync([Buffer.from("protocol_state")], programId)[0];
+};
+
+/**
+ * Get the PDA for a proposal
+ * @param id
+ * @param programId
+ * @returns
+ */
+export const getProposalPDA = (id: number, programId: PublicKey): PublicKey => {
+  const idBuffer = Buffer.allocUnsafe(8);
+  idBuffer.writeBigUInt64LE(BigInt(id), 0);
+
+  return PublicKey.findProgramAddressSync([Buffer.from("proposal"), idBuffer], programId)[0];
 };
 
 /**
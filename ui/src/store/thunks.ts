@@ -30,6 +30,7 @@ export const addRegistrar = createAsyncThunk<
     firstName: string;
     lastName: string;
     idNumber: string;
+    districts: string[];
     walletAddress: string;
     adminAuthority: PublicKey;
   },
@@ -37,7 +38,7 @@ export const addRegistrar = createAsyncThunk<
 >(
   "registrars/addRegistrar",
   async (
-    { registrarService, firstName, lastName, idNumber, walletAddress, adminAuthority },
+    { registrarService, firstName, lastName, idNumber, districts, walletAddress, adminAuthority },
     { rejectWithValue }
   ) => {
     try {
@@ -47,6 +48,7 @@ export const addRegistrar = createAsyncThunk<
         firstName,
         lastName,
         idNumber,
+        districts,
         registrarAuthority,
         adminAuthority
       );
@@ -57,6 +59,7 @@ export const addRegistrar = createAsyncThunk<
         idNumber,
         authority: registrarAuthority.toString(),
         addedBy: adminAuthority.toString(),
+        districts,
         isActive: true,
         bump: 0, // We don't know the bump, but it's not critical
       };
@@ -10,9 +10,18 @@ export interface GlobalState {
 export interface ProtocolState {
   admins: PublicKey[];
   isPaused: boolean;
+  proposalCount: number;
+  config: ProtocolConfig;
   bump: number;
 }
 
+export interface ProtocolConfig {
+  approvalThreshold: number;
+  searchValidityDays: number;
+  escrowPeriodDays: number;
+  agreementValidityDays: number;
+}
+
 export interface Admin {
   firstName: string;
   lastName: string;
@@ -27,6 +36,7 @@ export interface Registrar {
   idNumber: string;
   authority: PublicKey;
   addedBy: PublicKey;
+  districts: string[];
   isActive: boolean;
   bump: number;
 }
@@ -38,6 +48,7 @@ export interface SerializedRegistrar {
   idNumber: string;
   authority: string;
   addedBy: string;
+  districts: string[];
   isActive: boolean;
   bump: number;
 }